@@ -0,0 +1,66 @@
+//! Analyzes a synthetic polyglot repository end to end.
+//!
+//! The file count defaults to 50,000 and can be lowered for a quick run:
+//! `BUDDY_BENCH_FILES=2000 cargo bench --bench aggregation`.
+
+use std::fs;
+use std::path::Path;
+use buddy::analyzer::ProjectAnalyzer;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const DEFAULT_FILE_COUNT: usize = 50_000;
+
+fn write_file(root: &Path, relative: String, content: String) {
+    let path = root.join(relative);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+/// Spreads `count` small Go, Python, TypeScript and Rust sources over
+/// package-sized directories, plus the manifests a monorepo would have.
+fn synthetic_repo(count: usize) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+
+    write_file(root, "go.mod".into(), "module example.com/mono\n\ngo 1.22\n\nrequire github.com/gin-gonic/gin v1.9.1\n".into());
+    write_file(root, "package.json".into(), r#"{ "scripts": { "build": "tsc", "test": "jest" } }"#.into());
+
+    for i in 0..count {
+        let package = format!("pkg{:03}", i / 100);
+        match i % 4 {
+            0 => write_file(root, format!("internal/{}/service_{}.go", package, i), format!(
+                "package {package}\n\ntype Service{i} struct {{ repo Repository }}\n\nfunc NewService{i}(repo Repository) *Service{i} {{\n\treturn &Service{i}{{repo: repo}}\n}}\n\nfunc (s *Service{i}) Find(id int) (string, error) {{\n\tvalue, err := s.repo.Get(id)\n\tif err != nil {{\n\t\treturn \"\", err\n\t}}\n\treturn value, nil\n}}\n"
+            )),
+            1 => write_file(root, format!("services/{}/handler_{}.py", package, i), format!(
+                "class Handler{i}:\n    def __init__(self, repository):\n        self.repository = repository\n\n    def handle(self, item_id):\n        try:\n            return self.repository.get(item_id)\n        except KeyError:\n            return None\n"
+            )),
+            2 => write_file(root, format!("web/{}/component{}.ts", package, i), format!(
+                "export class Component{i} {{\n  constructor(private readonly api: Api) {{}}\n\n  async load(id: number): Promise<string> {{\n    const response = await this.api.get(`/items/${{id}}`);\n    return response.body;\n  }}\n}}\n"
+            )),
+            _ => write_file(root, format!("crates/{}/src/module_{}.rs", package, i), format!(
+                "pub struct Module{i} {{\n    name: String,\n}}\n\nimpl Module{i} {{\n    pub fn new(name: &str) -> Self {{\n        Self {{ name: name.to_string() }}\n    }}\n\n    pub fn name(&self) -> Option<&str> {{\n        Some(&self.name)\n    }}\n}}\n"
+            )),
+        }
+    }
+    dir
+}
+
+fn bench_analyze(c: &mut Criterion) {
+    let count = std::env::var("BUDDY_BENCH_FILES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_FILE_COUNT);
+    let repo = synthetic_repo(count);
+    let analyzer = ProjectAnalyzer::new(repo.path());
+
+    c.bench_function(&format!("analyze {} files", count), |b| {
+        b.iter(|| analyzer.analyze().unwrap())
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench_analyze
+}
+criterion_main!(benches);
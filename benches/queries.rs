@@ -0,0 +1,119 @@
+//! Per-file analysis cost with the cached query sets, compared with compiling
+//! every query again for each file as the analyzers used to.
+
+use std::path::Path;
+use buddy::languages::{compile_queries, get_analyzer};
+use buddy::parser::CodeParser;
+use buddy::plugins::PluginRegistry;
+use buddy::rules::AnalysisResult;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+const SAMPLES: &[(&str, &str)] = &[
+    ("service.go", r#"package service
+
+import "github.com/gin-gonic/gin"
+
+type UserService struct { repo Repository }
+
+func NewUserService(repo Repository) *UserService {
+	return &UserService{repo: repo}
+}
+
+func (s *UserService) Find(ctx *gin.Context, id int) (string, error) {
+	user, err := s.repo.Get(id)
+	if err != nil {
+		return "", err
+	}
+	return user.Name, nil
+}
+"#),
+    ("handler.py", r#"import requests
+
+class UserHandler:
+    def __init__(self, repository):
+        self.repository = repository
+
+    def handle(self, user_id):
+        try:
+            return self.repository.get(user_id)
+        except KeyError:
+            return None
+"#),
+    ("component.ts", r#"import express from "express";
+
+export class UserComponent {
+  constructor(private readonly api: Api) {}
+
+  async load(userId: number): Promise<string> {
+    const response = await this.api.get(`/users/${userId}`);
+    return response.body;
+  }
+}
+"#),
+    ("module.rs", r#"use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct User {
+    name: String,
+}
+
+impl User {
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        let name = std::fs::read_to_string(path)?;
+        Ok(Self { name })
+    }
+}
+"#),
+    ("UserService.java", r#"package com.example;
+
+import org.springframework.stereotype.Service;
+
+@Service
+public class UserService {
+    private final UserRepository repository;
+
+    public UserService(UserRepository repository) {
+        this.repository = repository;
+    }
+
+    public User find(long id) {
+        try {
+            return repository.findById(id);
+        } catch (IllegalStateException e) {
+            throw new UserNotFoundException(id);
+        }
+    }
+}
+"#),
+];
+
+fn bench_analyze_file(c: &mut Criterion) {
+    let mut group = c.benchmark_group("analyze_file");
+
+    for (file_name, content) in SAMPLES {
+        let parser = CodeParser::new(Path::new(file_name)).unwrap();
+        let tree = parser.parse(content).unwrap();
+        let analyzer = get_analyzer(parser.language, &PluginRegistry::default());
+        let language = parser.language.as_str();
+
+        group.bench_with_input(BenchmarkId::new("precompiled", language), &tree, |b, tree| {
+            b.iter(|| {
+                let mut result = AnalysisResult::default();
+                analyzer.analyze(content, tree, &mut result);
+                result
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("compiled_per_file", language), &tree, |b, tree| {
+            b.iter(|| {
+                compile_queries(parser.language);
+                let mut result = AnalysisResult::default();
+                analyzer.analyze(content, tree, &mut result);
+                result
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_analyze_file);
+criterion_main!(benches);
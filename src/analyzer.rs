@@ -1,56 +1,227 @@
 use std::path::{Path, PathBuf};
 use ignore::WalkBuilder;
+use ignore::overrides::{Override, OverrideBuilder};
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use rayon::prelude::*;
 use crate::parser::CodeParser;
-use crate::rules::AnalysisResult;
-use crate::rules::naming::Casing;
-use std::sync::{Arc, Mutex};
+use crate::rules::{AnalysisResult, LanguageFindings, Merge};
+use crate::secrets::SecretScanner;
+use crate::manifests::ManifestAnalyzer;
+use crate::scripts::ScriptAnalyzer;
+use crate::clones::{CloneDetector, FileTokens};
+use crate::cache::{AnalysisCache, CacheEntry, BUDDY_DIR};
+use crate::git_diff::GitDiff;
+use crate::config::{AnalyzerKind, BuddyConfig};
+use crate::custom_rules::CustomRuleSet;
+use crate::languages::rust::RustAnalyzer;
+use crate::plugins::PluginRegistry;
+use crate::rules::naming::{IdentifierKind, NamingViolations};
 
 pub struct ProjectAnalyzer {
     root_path: PathBuf,
+    use_cache: bool,
+    since: Option<String>,
+    config: BuddyConfig,
+    custom_rules: CustomRuleSet,
+    plugins: PluginRegistry,
 }
 
+/// What one rayon split has accumulated: the merged result, token streams
+/// for clone detection and the cache entries to write back.
+type Partial = (AnalysisResult, Vec<FileTokens>, Vec<(String, CacheEntry)>);
+
 impl ProjectAnalyzer {
     pub fn new(path: &Path) -> Self {
         Self {
             root_path: path.to_path_buf(),
+            use_cache: false,
+            since: None,
+            config: BuddyConfig::default(),
+            custom_rules: CustomRuleSet::default(),
+            plugins: PluginRegistry::default(),
         }
     }
 
+    /// Reuses per-file results from `.buddy/cache` for files whose content
+    /// hasn't changed, and refreshes the cache afterwards.
+    pub fn with_cache(mut self, enabled: bool) -> Self {
+        self.use_cache = enabled;
+        self
+    }
+
+    /// Limits per-file findings to files changed since `base_ref` while
+    /// conventions are still learned from the whole repository.
+    pub fn with_since(mut self, base_ref: Option<String>) -> Self {
+        self.since = base_ref;
+        self
+    }
+
+    /// Applies `.buddy.toml` settings: file globs, enabled analyzers,
+    /// architecture layers and extra tech-stack signatures.
+    pub fn with_config(mut self, config: BuddyConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Runs the project's own tree-sitter rules alongside the built-in analyzers.
+    pub fn with_custom_rules(mut self, rules: CustomRuleSet) -> Self {
+        self.custom_rules = rules;
+        self
+    }
+
+    /// Analyzes the languages in `plugins` besides the built-in ones.
+    pub fn with_plugins(mut self, plugins: PluginRegistry) -> Self {
+        self.plugins = plugins;
+        self
+    }
+
     pub fn analyze(&self) -> anyhow::Result<AnalysisResult> {
-        let files = self.collect_files();
-        let results = Arc::new(Mutex::new(AnalysisResult::default()));
-
-        files.par_iter().for_each(|file_path| {
-            if let Ok(content) = std::fs::read_to_string(file_path) {
-                self.analyze_file_pre_parser(file_path, &content, &results);
-                
-                if let Some(parser) = CodeParser::new(file_path) {
-                    if let Some(tree) = parser.parse(&content) {
-                        self.analyze_file(file_path, &content, &tree, &parser, &results);
+        let changed_files = match &self.since {
+            Some(base_ref) => Some(GitDiff::changed_files(&self.root_path, base_ref)?),
+            None => None,
+        };
+        let files = self.collect_files()?;
+        let settings = self.settings_fingerprint();
+        let cache = if self.use_cache { AnalysisCache::load(&self.root_path, &settings) } else { AnalysisCache::new(&settings) };
+
+        // Each rayon split folds its files into its own result, and the splits
+        // are merged pairwise in file order, so workers never share a lock.
+        let (mut final_result, token_streams, cache_entries) = files.par_iter()
+            .fold(
+                || -> Partial { Default::default() },
+                |(mut result, mut token_streams, mut cache_entries), file_path| {
+                    if let Ok(content) = std::fs::read_to_string(file_path) {
+                        let relative_path = self.relative_path(file_path);
+                        let content_hash = AnalysisCache::content_hash(&content);
+                        let entry = match cache.get(&relative_path, &content_hash) {
+                            Some(entry) => entry.clone(),
+                            None => {
+                                let (file_result, tokens) = self.analyze_single_file(file_path, &content);
+                                CacheEntry { content_hash, result: file_result, tokens }
+                            }
+                        };
+
+                        if self.use_cache {
+                            cache_entries.push((relative_path, entry.clone()));
+                        }
+                        result.merge(entry.result);
+                        token_streams.extend(entry.tokens);
                     }
-                }
+                    (result, token_streams, cache_entries)
+                },
+            )
+            .reduce(
+                || -> Partial { Default::default() },
+                |(mut result, mut token_streams, mut cache_entries), (other, other_streams, other_entries)| {
+                    result.merge(other);
+                    token_streams.extend(other_streams);
+                    cache_entries.extend(other_entries);
+                    (result, token_streams, cache_entries)
+                },
+            );
+
+        if self.use_cache {
+            let mut cache = AnalysisCache::new(&settings);
+            cache.entries = cache_entries.into_iter().collect();
+            if let Err(e) = cache.save(&self.root_path) {
+                eprintln!("Failed to update analysis cache: {}", e);
             }
-        });
+        }
 
-        let final_result = results.lock().unwrap().clone();
+        ScriptAnalyzer::finalize(&mut final_result);
+        CloneDetector::default().detect(token_streams, &mut final_result.dry);
+        final_result.dry.finalize_score();
+        final_result.security.hardcoded_secrets.sort_by(|a, b| (&a.file, a.line, a.column).cmp(&(&b.file, b.line, b.column)));
+        final_result.naming_violations = NamingViolations::new(
+            final_result.languages.iter().flat_map(|(language, findings)| findings.naming.violations(*language)).collect(),
+        );
+        // Verdicts describe the whole repository, so they come before `--since`
+        // narrows the per-file lists down to the changed files.
+        RustAnalyzer::finalize(&mut final_result);
+        final_result.error_handling.finalize_logging();
+        for findings in final_result.languages.values_mut() {
+            findings.error_handling.finalize_logging();
+        }
+        if let (Some(base_ref), Some(changed_files)) = (&self.since, &changed_files) {
+            GitDiff::scope(&mut final_result, base_ref, changed_files);
+        }
+        for findings in final_result.languages.values_mut() {
+            findings.naming.identifiers = Vec::new();
+        }
         Ok(final_result)
     }
 
+    /// Identifies the settings per-file results depend on, for the cache.
+    pub(crate) fn settings_fingerprint(&self) -> String {
+        format!("{}{}{}", self.config.fingerprint(), self.custom_rules.fingerprint(), self.plugins.fingerprint())
+    }
+
+    /// Everything buddy records for one file, independent of other files.
+    fn analyze_single_file(&self, path: &Path, content: &str) -> (AnalysisResult, Option<FileTokens>) {
+        let mut result = self.analyze_file_pre_parser(path, content);
+        let mut tokens = None;
+
+        let languages = self.config.is_enabled(AnalyzerKind::Languages);
+        let clones = self.config.is_enabled(AnalyzerKind::Clones);
+        let custom_rules = self.config.is_enabled(AnalyzerKind::CustomRules) && !self.custom_rules.is_empty();
+        if let Some(parser) = CodeParser::with_plugins(path, &self.plugins).filter(|_| languages || clones || custom_rules) {
+            if let Some(tree) = parser.parse(content) {
+                if languages {
+                    result.merge(self.analyze_file(path, content, &tree, &parser));
+                }
+                if clones {
+                    tokens = Some(CloneDetector::tokenize(&self.relative_path(path), content, &tree));
+                }
+                if custom_rules {
+                    result.custom_rules.findings = self.custom_rules.check(parser.language, &self.relative_path(path), content, &tree);
+                }
+            }
+        }
+        (result, tokens)
+    }
+
     fn analyze_file_pre_parser(
         &self,
         path: &Path,
-        _content: &str,
-        results: &Arc<Mutex<AnalysisResult>>,
-    ) {
+        content: &str,
+    ) -> AnalysisResult {
+        let mut result = AnalysisResult::default();
         let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
         let file_name_lower = file_name.to_lowercase();
+
+        // Secrets are scanned in every file, including ones CodeParser can't parse (.env, YAML, JSON)
+        if self.config.is_enabled(AnalyzerKind::Secrets) && SecretScanner::should_scan(file_name) {
+            result.security.hardcoded_secrets = SecretScanner::scan(&self.relative_path(path), content);
+        }
         
+        // Dependency manifests and lockfiles
+        if self.config.is_enabled(AnalyzerKind::Dependencies) && ManifestAnalyzer::is_manifest(file_name) {
+            ManifestAnalyzer::analyze_with_signatures(file_name, content, &self.config.tech_stack, &mut result.tech_stack);
+        }
+
+        // Commands the project defines (package.json scripts, Makefile targets, CI steps, ...)
+        let relative_path = self.relative_path(path);
+        if self.config.is_enabled(AnalyzerKind::Commands) && ScriptAnalyzer::is_script_source(&relative_path) {
+            ScriptAnalyzer::analyze(&relative_path, content, &mut result.commands);
+        }
+
         // Architecture detection based on directory structure
-        let mut layers = Vec::new();
+        let layers = &mut result.architecture.layers;
         let mut architecture_pattern = String::new();
+        let custom_layers = &self.config.architecture.layers;
+        let detect_architecture = self.config.is_enabled(AnalyzerKind::Architecture);
 
-        if let Some(parent) = path.parent() {
+        if detect_architecture && !custom_layers.is_empty() {
+            let directories: Vec<&str> = relative_path.split('/').rev().skip(1).collect();
+            for (layer, names) in custom_layers {
+                if names.iter().any(|name| directories.contains(&name.as_str())) {
+                    layers.push(layer.clone());
+                }
+            }
+            if !layers.is_empty() {
+                architecture_pattern = self.config.architecture.pattern.clone().unwrap_or_else(|| "Custom".to_string());
+            }
+        } else if let Some(parent) = path.parent().filter(|_| detect_architecture) {
             let parent_str = parent.to_string_lossy();
             if parent_str.contains("domain") || parent_str.contains("usecase") || parent_str.contains("repository") || parent_str.contains("delivery") {
                 architecture_pattern = "Clean Architecture".to_string();
@@ -68,51 +239,82 @@ impl ProjectAnalyzer {
             }
         }
 
+        result.architecture.pattern = architecture_pattern;
+
         // Heuristic for Config Sources
-        if path.extension().is_some_and(|ext| ext == "env" || ext == "yaml" || ext == "yml" || ext == "json") 
-           || file_name_lower.contains("config") || file_name_lower == "properties.yaml" {
-             let mut global_results = results.lock().unwrap();
+        let config_file = path.extension().is_some_and(|ext| ext == "env" || ext == "yaml" || ext == "yml" || ext == "json")
+            || file_name_lower.contains("config") || file_name_lower == "properties.yaml";
+        if config_file && self.config.is_enabled(AnalyzerKind::ConfigFiles) {
              let source = if file_name_lower == "properties.yaml" || file_name_lower == "application.yaml" {
                  file_name.to_string()
              } else {
                  path.extension().and_then(|e| e.to_str()).unwrap_or("config").to_string()
              };
-             
-             if !global_results.config.config_sources.contains(&source) {
-                 global_results.config.config_sources.push(source);
-             }
+             result.config.config_sources.push(source);
              if file_name_lower == "properties.yaml" {
-                 global_results.config.type_safety = "Structured (Properties)".to_string();
+                 result.config.type_safety = "Structured (Properties)".to_string();
              }
         }
 
-        // Merge architecture info
-        if !architecture_pattern.is_empty() {
-            let mut global_results = results.lock().unwrap();
-            if global_results.architecture.pattern.is_empty() {
-                global_results.architecture.pattern = architecture_pattern;
-            }
-            for layer in layers {
-                if !global_results.architecture.layers.contains(&layer) {
-                    global_results.architecture.layers.push(layer);
-                }
-            }
-        }
+        result
+    }
+
+    /// Path relative to the analyzed root, with `/` separators, as used in
+    /// findings so reports are stable across machines.
+    fn relative_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root_path)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
     }
 
-    fn collect_files(&self) -> Vec<PathBuf> {
+    fn collect_files(&self) -> anyhow::Result<Vec<PathBuf>> {
         let mut files = Vec::new();
+        let include = self.include_globs()?;
         let walker = WalkBuilder::new(&self.root_path)
             .hidden(false)
             .git_ignore(true)
+            .overrides(self.exclude_globs()?)
+            .filter_entry(|entry| entry.file_name() != BUDDY_DIR)
             .build();
 
         for entry in walker.flatten() {
-            if entry.file_type().map(|ft| ft.is_file()).unwrap_or(false) {
+            let is_file = entry.file_type().map(|ft| ft.is_file()).unwrap_or(false);
+            if is_file && include.as_ref().is_none_or(|globs| globs.is_match(self.relative_path(entry.path()))) {
                 files.push(entry.path().to_path_buf());
             }
         }
-        files
+        // Directory order differs between filesystems; the merge keeps input order.
+        files.sort();
+        Ok(files)
+    }
+
+    /// `exclude` from `.buddy.toml`, applied on top of `.gitignore`.
+    fn exclude_globs(&self) -> anyhow::Result<Override> {
+        let mut builder = OverrideBuilder::new(&self.root_path);
+        for glob in &self.config.exclude {
+            builder.add(&format!("!{}", glob))?;
+        }
+        Ok(builder.build()?)
+    }
+
+    /// `include` from `.buddy.toml`, or `None` to keep every file. Matched
+    /// against the walked files rather than passed to the walker as overrides,
+    /// since those would pull gitignored files back in.
+    fn include_globs(&self) -> anyhow::Result<Option<GlobSet>> {
+        if self.config.include.is_empty() {
+            return Ok(None);
+        }
+        let mut builder = GlobSetBuilder::new();
+        for glob in &self.config.include {
+            let glob = glob.trim_start_matches('/');
+            builder.add(Glob::new(glob)?);
+            // Like in .gitignore, a pattern without a slash matches at any depth.
+            if !glob.contains('/') {
+                builder.add(Glob::new(&format!("**/{}", glob))?);
+            }
+        }
+        Ok(Some(builder.build()?))
     }
 
     fn analyze_file(
@@ -121,24 +323,42 @@ impl ProjectAnalyzer {
         content: &str,
         tree: &tree_sitter::Tree,
         parser: &CodeParser,
-        results: &Arc<Mutex<AnalysisResult>>,
-    ) {
+    ) -> AnalysisResult {
         let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
         
-        let analyzer = crate::languages::get_analyzer(parser.language);
+        let relative_path = self.relative_path(path);
+
+        let analyzer = crate::languages::get_analyzer(parser.language, &self.plugins);
         let mut local_result = AnalysisResult::default();
         analyzer.analyze(content, tree, &mut local_result);
+        for block in &mut local_result.dry.duplicated_blocks {
+            block.file = relative_path.clone();
+        }
+        for usage in &mut local_result.error_handling.unwrap_usage {
+            usage.file = relative_path.clone();
+        }
+        for block in &mut local_result.security.unsafe_blocks {
+            block.file = relative_path.clone();
+        }
+        for print in &mut local_result.error_handling.logging.stray_prints {
+            print.file = relative_path.clone();
+        }
 
         // Update naming conventions for file
         if !file_name.is_empty() {
-            if let Some(file_stem) = path.file_stem().and_then(|n| n.to_str()) {
-                local_result.naming.file_naming = crate::rules::naming::NamingConvention::detect_casing(file_stem);
+            // `user.service.spec.ts` is named `user`
+            if let Some(base_name) = file_name.split('.').next().filter(|n| !n.is_empty()) {
+                local_result.naming.record_identifier(IdentifierKind::File, base_name, 1);
             }
             
             // Heuristic for Test Location
             if file_name.contains("test") || file_name.contains("_test") || file_name.contains(".spec.") {
+                // Printing from tests is fine; only production code has stray prints.
+                local_result.error_handling.logging.stray_prints.clear();
                 local_result.testing.test_location = "In-project/In-file".to_string();
-                local_result.testing.naming_pattern = "test_* or *_test".to_string();
+                if local_result.testing.naming_pattern.is_empty() {
+                    local_result.testing.naming_pattern = "test_* or *_test".to_string();
+                }
             }
 
             if file_name.ends_with("_mock.go") {
@@ -146,100 +366,17 @@ impl ProjectAnalyzer {
             }
         }
 
-        // Heuristic for Security (Hardcoded Secrets)
-        let secret_regex = regex::Regex::new(r#"(?i)(api_key|secret|password|token)\s*[:=]\s*["'][a-zA-Z0-9]{10,}["']"#).unwrap();
-        if secret_regex.is_match(content) {
-            local_result.security.hardcoded_secrets.push(format!("Potential secret in {:?}", path));
-        }
-
-        let mut global_results = results.lock().unwrap();
-        
-        // Update language counts
-        *global_results.language_counts.entry(parser.language.as_str().to_string()).or_insert(0) += 1;
-        
-        // Merge results
-        if global_results.naming.variable_casing == Casing::Unknown {
-            global_results.naming.variable_casing = local_result.naming.variable_casing;
-        }
-        if global_results.naming.function_casing == Casing::Unknown {
-            global_results.naming.function_casing = local_result.naming.function_casing;
-        }
-        if global_results.naming.class_struct_naming == Casing::Unknown {
-            global_results.naming.class_struct_naming = local_result.naming.class_struct_naming;
-        }
-        if global_results.naming.file_naming == Casing::Unknown {
-            global_results.naming.file_naming = local_result.naming.file_naming;
-        }
-        if global_results.naming.interface_prefix.is_none() {
-            global_results.naming.interface_prefix = local_result.naming.interface_prefix;
-        }
-
-        if global_results.testing.test_location.is_empty() {
-            global_results.testing.test_location = local_result.testing.test_location;
-        }
-        if global_results.testing.naming_pattern.is_empty() {
-            global_results.testing.naming_pattern = local_result.testing.naming_pattern;
-        }
-        if global_results.testing.mocking_strategy.is_empty() || global_results.testing.mocking_strategy == "N/A" {
-            global_results.testing.mocking_strategy = local_result.testing.mocking_strategy;
-        }
-        if global_results.testing.assertion_style.is_empty() {
-            global_results.testing.assertion_style = local_result.testing.assertion_style;
+        for identifier in &mut local_result.naming.identifiers {
+            identifier.file = relative_path.clone();
         }
 
-        if global_results.config.type_safety.is_empty() || global_results.config.type_safety == "N/A" {
-            global_results.config.type_safety = local_result.config.type_safety;
-        }
-        
-        for secret in local_result.security.hardcoded_secrets {
-            if !global_results.security.hardcoded_secrets.contains(&secret) {
-                global_results.security.hardcoded_secrets.push(secret);
-            }
-        }
-        
-        for pattern in local_result.error_handling.failure_patterns {
-            if !global_results.error_handling.failure_patterns.contains(&pattern) {
-                global_results.error_handling.failure_patterns.push(pattern);
-            }
-        }
-
-        for pattern in local_result.di.injection_patterns {
-            if !global_results.di.injection_patterns.contains(&pattern) {
-                global_results.di.injection_patterns.push(pattern);
-            }
-        }
-
-        for pattern in local_result.design_patterns.patterns {
-            if !global_results.design_patterns.patterns.contains(&pattern) {
-                global_results.design_patterns.patterns.push(pattern);
-            }
-        }
-
-        // Merge DRY Analysis
-        for block in local_result.dry.duplicated_blocks {
-            if !global_results.dry.duplicated_blocks.contains(&block) {
-                global_results.dry.duplicated_blocks.push(block);
-            }
-        }
-        if local_result.dry.duplication_score > global_results.dry.duplication_score {
-            global_results.dry.duplication_score = local_result.dry.duplication_score;
-        }
-
-        // Merge Tech Stack
-        for f in local_result.tech_stack.frameworks {
-            if !global_results.tech_stack.frameworks.contains(&f) {
-                global_results.tech_stack.frameworks.push(f);
-            }
-        }
-        for l in local_result.tech_stack.libraries {
-            if !global_results.tech_stack.libraries.contains(&l) {
-                global_results.tech_stack.libraries.push(l);
-            }
-        }
-        for d in local_result.tech_stack.databases {
-            if !global_results.tech_stack.databases.contains(&d) {
-                global_results.tech_stack.databases.push(d);
-            }
-        }
+        *local_result.language_counts.entry(parser.language.as_str().to_string()).or_insert(0) += 1;
+        // Identifiers are only kept per language, where the conventions they
+        // are checked against live.
+        let identifiers = std::mem::take(&mut local_result.naming.identifiers);
+        let mut findings = LanguageFindings::from_file(&local_result);
+        findings.naming.identifiers = identifiers;
+        local_result.languages.insert(parser.language, findings);
+        local_result
     }
 }
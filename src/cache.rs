@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use anyhow::Context;
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+use crate::clones::FileTokens;
+use crate::rules::AnalysisResult;
+
+/// Directory, relative to the analyzed root, that holds buddy's own state.
+pub const BUDDY_DIR: &str = ".buddy";
+pub const CACHE_DIR: &str = ".buddy/cache";
+const CACHE_FILE: &str = "analysis.json";
+
+/// Bumped whenever analyzers change what they record for a file, so results
+/// produced by an older buddy are never reused.
+const CACHE_VERSION: u32 = 18;
+
+/// Per-file analysis results from a previous run, keyed by relative path and
+/// only valid while the file's content hash, the analyzer version and the
+/// settings that affect per-file results (`.buddy.toml`, custom rules) all match.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnalysisCache {
+    version: u32,
+    analyzer_version: String,
+    settings_fingerprint: String,
+    pub entries: BTreeMap<String, CacheEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CacheEntry {
+    pub content_hash: String,
+    pub result: AnalysisResult,
+    /// Present for files tree-sitter could parse; needed for clone detection.
+    pub tokens: Option<FileTokens>,
+}
+
+impl AnalysisCache {
+    /// An empty cache for results computed under the settings identified by
+    /// `settings_fingerprint`.
+    pub fn new(settings_fingerprint: &str) -> Self {
+        Self {
+            version: CACHE_VERSION,
+            analyzer_version: env!("CARGO_PKG_VERSION").to_string(),
+            settings_fingerprint: settings_fingerprint.to_string(),
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Loads the cache under `root`. A missing, unreadable or outdated cache
+    /// is treated as empty: the worst case is a full re-analysis.
+    pub fn load(root: &Path, settings_fingerprint: &str) -> Self {
+        let empty = Self::new(settings_fingerprint);
+        let path = root.join(CACHE_DIR).join(CACHE_FILE);
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .filter(|cache| {
+                cache.version == empty.version
+                    && cache.analyzer_version == empty.analyzer_version
+                    && cache.settings_fingerprint == empty.settings_fingerprint
+            })
+            .unwrap_or(empty)
+    }
+
+    /// Writes the cache, replacing the previous one. Only files analyzed in
+    /// this run are included, so entries for deleted files are pruned.
+    pub fn save(&self, root: &Path) -> anyhow::Result<()> {
+        let dir = root.join(CACHE_DIR);
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        // The cache is machine-local state and should never be committed.
+        std::fs::write(dir.join(".gitignore"), "*\n")
+            .with_context(|| format!("Failed to write {}", dir.join(".gitignore").display()))?;
+
+        let path = dir.join(CACHE_FILE);
+        std::fs::write(&path, serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn get(&self, relative_path: &str, content_hash: &str) -> Option<&CacheEntry> {
+        self.entries.get(relative_path).filter(|entry| entry.content_hash == content_hash)
+    }
+
+    pub fn content_hash(content: &str) -> String {
+        Sha256::digest(content.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::ProjectAnalyzer;
+
+    fn analyze(root: &Path) -> AnalysisResult {
+        ProjectAnalyzer::new(root).with_cache(true).analyze().unwrap()
+    }
+
+    fn load(root: &Path) -> AnalysisCache {
+        AnalysisCache::load(root, &ProjectAnalyzer::new(root).settings_fingerprint())
+    }
+
+    #[test]
+    fn test_unchanged_files_come_from_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("main.go"), "package main\n\nfunc NewServer() {}\n").unwrap();
+        std::fs::write(dir.path().join("util.go"), "package main\n\nfunc helperFunc() {}\n").unwrap();
+
+        let first = analyze(dir.path());
+        let cache = load(dir.path());
+        assert_eq!(cache.entries.len(), 2);
+
+        // Plant a marker in the cached entry: it only shows up if the entry is reused.
+        let mut cache = cache;
+        cache.entries.get_mut("util.go").unwrap().result.design_patterns.patterns.push("From Cache".to_string());
+        cache.save(dir.path()).unwrap();
+
+        let second = analyze(dir.path());
+        assert!(second.design_patterns.patterns.contains(&"From Cache".to_string()));
+        assert_eq!(second.language_counts, first.language_counts);
+    }
+
+    #[test]
+    fn test_changed_and_deleted_files_are_refreshed() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.py"), "def load_data():\n    pass\n").unwrap();
+        std::fs::write(dir.path().join("b.py"), "def save_data():\n    pass\n").unwrap();
+        analyze(dir.path());
+
+        std::fs::write(dir.path().join("a.py"), "def loadData():\n    pass\n").unwrap();
+        std::fs::remove_file(dir.path().join("b.py")).unwrap();
+        let result = analyze(dir.path());
+
+        let cache = load(dir.path());
+        assert_eq!(cache.entries.keys().collect::<Vec<_>>(), vec!["a.py"]);
+        assert_eq!(result.language_counts["Python"], 1);
+        assert_eq!(result.naming.function_casing, crate::rules::naming::Casing::CamelCase);
+    }
+}
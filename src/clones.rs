@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+use crate::rules::dry::{CloneLocation, CloneType, CodeClone, DRYAnalysis};
+
+/// Shortest token sequence reported as a clone.
+pub const MIN_CLONE_TOKENS: usize = 50;
+
+/// Windows sharing a hash are only compared against this many earlier
+/// occurrences, so boilerplate repeated hundreds of times stays cheap.
+const MAX_OCCURRENCES: usize = 16;
+
+const IDENTIFIER: &str = "$id";
+const LITERAL: &str = "$lit";
+
+/// Token stream of one source file. Identifiers and literals are abstracted
+/// in `normalized` (Type-2 matching) while `raw` keeps their text (Type-1).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileTokens {
+    pub file: String,
+    normalized: Vec<u64>,
+    raw: Vec<u64>,
+    lines: Vec<usize>,
+}
+
+impl FileTokens {
+    pub fn len(&self) -> usize {
+        self.normalized.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.normalized.is_empty()
+    }
+}
+
+/// Finds Type-1 and Type-2 clones across the whole repository by comparing
+/// normalized tree-sitter token streams.
+pub struct CloneDetector {
+    min_tokens: usize,
+}
+
+impl Default for CloneDetector {
+    fn default() -> Self {
+        Self::new(MIN_CLONE_TOKENS)
+    }
+}
+
+impl CloneDetector {
+    pub fn new(min_tokens: usize) -> Self {
+        Self { min_tokens: min_tokens.max(1) }
+    }
+
+    /// Flattens `tree` into tokens, skipping comments. String and number
+    /// literals become a single token even when the grammar splits them.
+    pub fn tokenize(file: &str, content: &str, tree: &tree_sitter::Tree) -> FileTokens {
+        let mut tokens = FileTokens {
+            file: file.to_string(),
+            normalized: Vec::new(),
+            raw: Vec::new(),
+            lines: Vec::new(),
+        };
+        let source = content.as_bytes();
+        let mut cursor = tree.walk();
+
+        'walk: loop {
+            let node = cursor.node();
+            let kind = node.kind();
+            let leaf = node.child_count() == 0;
+            let literal = node.is_named() && Self::is_literal(kind);
+
+            if !kind.contains("comment") && (leaf || literal) {
+                let text = node.utf8_text(source).unwrap_or("");
+                if !text.trim().is_empty() {
+                    let normalized = if literal {
+                        LITERAL
+                    } else if node.is_named() && Self::is_identifier(kind) {
+                        IDENTIFIER
+                    } else {
+                        text
+                    };
+                    tokens.normalized.push(Self::hash(normalized));
+                    tokens.raw.push(Self::hash(text));
+                    tokens.lines.push(node.start_position().row + 1);
+                }
+            } else if !kind.contains("comment") && cursor.goto_first_child() {
+                continue;
+            }
+
+            while !cursor.goto_next_sibling() {
+                if !cursor.goto_parent() {
+                    break 'walk;
+                }
+            }
+        }
+        tokens
+    }
+
+    /// Records every clone pair in `dry` along with the percentage of tokens
+    /// that are duplicated somewhere else in the repository.
+    pub fn detect(&self, mut files: Vec<FileTokens>, dry: &mut DRYAnalysis) {
+        files.sort_by(|a, b| a.file.cmp(&b.file));
+
+        let window = self.min_tokens;
+        let mut seen: HashMap<u64, Vec<(usize, usize)>> = HashMap::new();
+        let mut duplicated: Vec<Vec<bool>> = files.iter().map(|f| vec![false; f.len()]).collect();
+
+        for (fb, file) in files.iter().enumerate() {
+            // Positions already inside a reported duplicate don't start new clones.
+            let mut covered_until = 0;
+
+            for (j, hash) in Self::window_hashes(&file.normalized, window).into_iter().enumerate() {
+                let occurrences = seen.entry(hash).or_default();
+                if j >= covered_until {
+                    for &(fa, i) in occurrences.iter() {
+                        if fa == fb && i + window > j {
+                            continue;
+                        }
+                        let a = &files[fa].normalized;
+                        let b = &file.normalized;
+                        if a[i..i + window] != b[j..j + window] {
+                            continue;
+                        }
+
+                        let mut len = window;
+                        while i + len < a.len()
+                            && j + len < b.len()
+                            && a[i + len] == b[j + len]
+                            && !(fa == fb && i + len >= j)
+                        {
+                            len += 1;
+                        }
+
+                        let clone_type = if files[fa].raw[i..i + len] == file.raw[j..j + len] {
+                            CloneType::Type1
+                        } else {
+                            CloneType::Type2
+                        };
+                        dry.clones.push(CodeClone {
+                            clone_type,
+                            token_count: len,
+                            original: Self::location(&files[fa], i, len),
+                            duplicate: Self::location(file, j, len),
+                        });
+                        duplicated[fa][i..i + len].iter_mut().for_each(|d| *d = true);
+                        duplicated[fb][j..j + len].iter_mut().for_each(|d| *d = true);
+                        covered_until = j + len;
+                        break;
+                    }
+                }
+                if occurrences.len() < MAX_OCCURRENCES {
+                    occurrences.push((fb, j));
+                }
+            }
+        }
+
+        let total: usize = files.iter().map(FileTokens::len).sum();
+        let duplicated: usize = duplicated.iter().map(|d| d.iter().filter(|d| **d).count()).sum();
+        dry.duplication_percentage = if total == 0 {
+            0.0
+        } else {
+            duplicated as f64 * 100.0 / total as f64
+        };
+    }
+
+    fn location(file: &FileTokens, start: usize, len: usize) -> CloneLocation {
+        CloneLocation {
+            file: file.file.clone(),
+            start_line: file.lines[start],
+            end_line: file.lines[start + len - 1],
+        }
+    }
+
+    /// Rolling polynomial hash of every `window`-token slice of `tokens`.
+    fn window_hashes(tokens: &[u64], window: usize) -> Vec<u64> {
+        const BASE: u64 = 1_000_003;
+        if tokens.len() < window {
+            return Vec::new();
+        }
+
+        let top = (1..window).fold(1u64, |acc, _| acc.wrapping_mul(BASE));
+        let mut hash = tokens[..window].iter().fold(0u64, |acc, t| acc.wrapping_mul(BASE).wrapping_add(*t));
+        let mut hashes = vec![hash];
+        for i in window..tokens.len() {
+            hash = hash
+                .wrapping_sub(tokens[i - window].wrapping_mul(top))
+                .wrapping_mul(BASE)
+                .wrapping_add(tokens[i]);
+            hashes.push(hash);
+        }
+        hashes
+    }
+
+    fn is_identifier(kind: &str) -> bool {
+        kind == "identifier" || kind.ends_with("_identifier")
+    }
+
+    fn is_literal(kind: &str) -> bool {
+        kind.contains("string")
+            || kind.ends_with("_literal")
+            || matches!(kind, "integer" | "float" | "number" | "char" | "rune_literal")
+    }
+
+    /// FNV-1a, so token hashes stay stable across Rust releases and can be cached.
+    fn hash(text: &str) -> u64 {
+        text.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::CodeParser;
+    use std::path::Path;
+
+    fn tokens(file: &str, content: &str) -> FileTokens {
+        let parser = CodeParser::new(Path::new(file)).unwrap();
+        let tree = parser.parse(content).unwrap();
+        CloneDetector::tokenize(file, content, &tree)
+    }
+
+    #[test]
+    fn test_type2_clone_across_files() {
+        let original = r#"package a
+
+// Sum adds up the values.
+func Sum(values []int) int {
+	total := 0
+	for _, v := range values {
+		if v > 10 {
+			total += v
+		}
+	}
+	return total
+}
+"#;
+        let renamed = r#"package b
+
+func unrelated() {}
+
+func Add(items []int) int {
+	acc := 0
+	for _, item := range items {
+		if item > 99 {
+			acc += item
+		}
+	}
+	return acc
+}
+"#;
+        let mut dry = DRYAnalysis::default();
+        CloneDetector::new(20).detect(vec![tokens("b.go", renamed), tokens("a.go", original)], &mut dry);
+
+        assert_eq!(dry.clones.len(), 1);
+        let clone = &dry.clones[0];
+        assert_eq!(clone.clone_type, CloneType::Type2);
+        assert_eq!(clone.original, CloneLocation { file: "a.go".to_string(), start_line: 4, end_line: 12 });
+        assert_eq!(clone.duplicate, CloneLocation { file: "b.go".to_string(), start_line: 5, end_line: 13 });
+        assert!(dry.duplication_percentage > 50.0 && dry.duplication_percentage < 100.0);
+    }
+
+    #[test]
+    fn test_identical_copy_is_type1_and_short_code_is_ignored() {
+        let code = "def load(path):\n    with open(path) as f:\n        data = f.read()\n    return data.strip().split(',')\n";
+        let mut dry = DRYAnalysis::default();
+        CloneDetector::new(10).detect(vec![tokens("a.py", code), tokens("b.py", code)], &mut dry);
+        assert_eq!(dry.clones.len(), 1);
+        assert_eq!(dry.clones[0].clone_type, CloneType::Type1);
+        assert_eq!(dry.duplication_percentage, 100.0);
+
+        let mut dry = DRYAnalysis::default();
+        CloneDetector::new(MIN_CLONE_TOKENS).detect(vec![tokens("a.py", code), tokens("b.py", code)], &mut dry);
+        assert!(dry.clones.is_empty());
+        assert_eq!(dry.duplication_percentage, 0.0);
+    }
+}
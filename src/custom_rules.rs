@@ -0,0 +1,272 @@
+use std::path::Path;
+use anyhow::Context;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use streaming_iterator::StreamingIterator;
+use tree_sitter::{Language, Query, QueryCursor};
+use crate::parser::SupportedLanguage;
+use crate::plugins::PluginRegistry;
+use crate::rules::custom::{CustomRuleFinding, Severity};
+
+/// Default location of project rules, relative to the analyzed root.
+pub const RULES_DIR: &str = ".buddy/rules";
+
+/// Capture that marks where a finding is reported. Without it, the first
+/// capture of the match is used.
+const VIOLATION_CAPTURE: &str = "violation";
+
+const DEFAULT_SECTION: &str = "General";
+
+/// `<rule>.toml`, next to `<rule>.scm`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleMetadata {
+    language: String,
+    message: String,
+    #[serde(default)]
+    severity: Severity,
+    section: Option<String>,
+    /// Gitignore-style globs limiting the files the rule applies to.
+    #[serde(default)]
+    paths: Vec<String>,
+}
+
+/// A tree-sitter query written by the project. Every match is a finding.
+pub struct CustomRule {
+    pub id: String,
+    pub language: SupportedLanguage,
+    pub severity: Severity,
+    pub message: String,
+    pub section: String,
+    paths: Option<GlobSet>,
+    /// The query compiled for each grammar files of `language` may be parsed with.
+    queries: Vec<(Language, Query)>,
+}
+
+impl CustomRule {
+    /// Compiles `<dir>/<id>.scm` with the metadata in `<dir>/<id>.toml`.
+    /// The rule may target any language in `plugins`.
+    pub fn load(scm_path: &Path, plugins: &PluginRegistry) -> anyhow::Result<Self> {
+        let id = scm_path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string();
+        let metadata_path = scm_path.with_extension("toml");
+        let metadata = std::fs::read_to_string(&metadata_path)
+            .with_context(|| format!("Missing metadata for rule `{}`: {}", id, metadata_path.display()))?;
+        let metadata: RuleMetadata = toml::from_str(&metadata)
+            .with_context(|| format!("Invalid metadata in {}", metadata_path.display()))?;
+        let source = std::fs::read_to_string(scm_path)
+            .with_context(|| format!("Failed to read {}", scm_path.display()))?;
+
+        let language = plugins.language(&metadata.language)
+            .with_context(|| format!("Unknown language `{}` in {}", metadata.language, metadata_path.display()))?;
+
+        // The main grammar must accept the query; TSX only gets it when it
+        // compiles there too, since it adds node types but renames none.
+        let mut queries = Vec::new();
+        for (i, grammar) in plugins.grammars(language).into_iter().enumerate() {
+            match Query::new(&grammar, &source) {
+                Ok(query) => queries.push((grammar, query)),
+                Err(e) if i == 0 => anyhow::bail!("Invalid query in {}: {}", scm_path.display(), e),
+                Err(_) => {}
+            }
+        }
+        let (_, main_query) = queries.first()
+            .with_context(|| format!("No grammar is available for `{}` in {}", metadata.language, metadata_path.display()))?;
+        if main_query.capture_names().is_empty() {
+            anyhow::bail!("Query in {} must capture at least one node, e.g. @{}", scm_path.display(), VIOLATION_CAPTURE);
+        }
+
+        let paths = if metadata.paths.is_empty() {
+            None
+        } else {
+            let mut builder = GlobSetBuilder::new();
+            for pattern in &metadata.paths {
+                builder.add(Glob::new(pattern).with_context(|| format!("Invalid path glob in {}", metadata_path.display()))?);
+            }
+            Some(builder.build()?)
+        };
+
+        Ok(Self {
+            id,
+            language,
+            severity: metadata.severity,
+            message: metadata.message,
+            section: metadata.section.unwrap_or_else(|| DEFAULT_SECTION.to_string()),
+            paths,
+            queries,
+        })
+    }
+
+    fn applies_to(&self, language: SupportedLanguage, relative_path: &str) -> bool {
+        self.language == language && self.paths.as_ref().is_none_or(|paths| paths.is_match(relative_path))
+    }
+
+    fn check(&self, content: &str, tree: &tree_sitter::Tree, findings: &mut Vec<CustomRuleFinding>) {
+        let grammar = tree.language().to_owned();
+        let Some((_, query)) = self.queries.iter().find(|(language, _)| *language == grammar) else { return };
+        let violation = query.capture_index_for_name(VIOLATION_CAPTURE);
+
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+        while let Some(m) = matches.next() {
+            let capture = m.captures.iter()
+                .find(|capture| Some(capture.index) == violation)
+                .or_else(|| m.captures.first());
+            let Some(capture) = capture else { continue };
+
+            let position = capture.node.start_position();
+            let finding = CustomRuleFinding {
+                rule_id: self.id.clone(),
+                file: String::new(),
+                line: position.row + 1,
+                column: position.column + 1,
+                severity: self.severity,
+                message: self.message.clone(),
+                section: self.section.clone(),
+            };
+            if !findings.contains(&finding) {
+                findings.push(finding);
+            }
+        }
+    }
+}
+
+/// Every rule in a project's rules directory.
+#[derive(Default)]
+pub struct CustomRuleSet {
+    rules: Vec<CustomRule>,
+    fingerprint: String,
+}
+
+impl CustomRuleSet {
+    /// Loads every `.scm` file in `dir`. A missing directory means no rules;
+    /// a broken rule is an error so it doesn't go unnoticed.
+    pub fn load(dir: &Path, plugins: &PluginRegistry) -> anyhow::Result<Self> {
+        if !dir.is_dir() {
+            return Ok(Self::default());
+        }
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .with_context(|| format!("Failed to read {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "scm"))
+            .collect();
+        paths.sort();
+
+        let mut hasher = Sha256::new();
+        let mut rules = Vec::new();
+        for path in &paths {
+            rules.push(CustomRule::load(path, plugins)?);
+            // Rule sources decide per-file results, so they are part of the cache key.
+            hasher.update(std::fs::read(path)?);
+            hasher.update(std::fs::read(path.with_extension("toml"))?);
+        }
+        let fingerprint = if rules.is_empty() {
+            String::new()
+        } else {
+            hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect()
+        };
+        Ok(Self { rules, fingerprint })
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Identifies the loaded rules; empty when there are none.
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    /// Findings of every rule for `language` that applies to `relative_path`.
+    pub fn check(&self, language: SupportedLanguage, relative_path: &str, content: &str, tree: &tree_sitter::Tree) -> Vec<CustomRuleFinding> {
+        let mut findings = Vec::new();
+        for rule in self.rules.iter().filter(|rule| rule.applies_to(language, relative_path)) {
+            rule.check(content, tree, &mut findings);
+        }
+        for finding in &mut findings {
+            finding.file = relative_path.to_string();
+        }
+        findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::CodeParser;
+
+    fn write_rule(dir: &Path, id: &str, query: &str, metadata: &str) {
+        std::fs::write(dir.join(format!("{}.scm", id)), query).unwrap();
+        std::fs::write(dir.join(format!("{}.toml", id)), metadata).unwrap();
+    }
+
+    fn check(rules: &CustomRuleSet, file: &str, content: &str) -> Vec<CustomRuleFinding> {
+        let parser = CodeParser::new(Path::new(file)).unwrap();
+        let tree = parser.parse(content).unwrap();
+        rules.check(parser.language, file, content, &tree)
+    }
+
+    #[test]
+    fn test_forbidden_call_limited_to_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        write_rule(dir.path(), "no-println", r#"
+            (call_expression
+              function: (selector_expression
+                operand: (identifier) @pkg
+                field: (field_identifier) @fn)
+              (#eq? @pkg "fmt")
+              (#eq? @fn "Println")) @violation
+        "#, "language = \"go\"\nmessage = \"Use the logger instead of fmt.Println\"\nseverity = \"error\"\npaths = [\"internal/**\"]\n");
+        let rules = CustomRuleSet::load(dir.path(), &PluginRegistry::default()).unwrap();
+
+        let code = "package app\n\nfunc Run() {\n\tfmt.Println(\"starting\")\n}\n";
+        let findings = check(&rules, "internal/app/run.go", code);
+        assert_eq!(findings.len(), 1);
+        assert_eq!((findings[0].line, findings[0].column), (4, 2));
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[0].section, "General");
+        assert!(check(&rules, "cmd/main.go", code).is_empty());
+    }
+
+    #[test]
+    fn test_required_call_through_not_match() {
+        let dir = tempfile::tempdir().unwrap();
+        write_rule(dir.path(), "handler-validates", r#"
+            (method_declaration
+              name: (field_identifier) @violation
+              body: (block) @body
+              (#match? @violation "Handler$")
+              (#not-match? @body "ctx\\.Validate\\("))
+        "#, "language = \"Go\"\nmessage = \"Handlers must call ctx.Validate\"\nsection = \"HTTP Handlers\"\n");
+        let rules = CustomRuleSet::load(dir.path(), &PluginRegistry::default()).unwrap();
+
+        let code = r#"package api
+
+func (s *Server) UserHandler(ctx *Context) {
+	ctx.Validate()
+}
+
+func (s *Server) OrderHandler(ctx *Context) {
+	ctx.JSON(200, nil)
+}
+"#;
+        let findings = check(&rules, "api/server.go", code);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line, 7);
+        assert_eq!(findings[0].rule_id, "handler-validates");
+        assert_eq!(findings[0].section, "HTTP Handlers");
+    }
+
+    #[test]
+    fn test_invalid_rule_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        write_rule(dir.path(), "broken", "(no_such_node) @violation", "language = \"python\"\nmessage = \"x\"\n");
+        let error = CustomRuleSet::load(dir.path(), &PluginRegistry::default()).err().unwrap();
+        assert!(error.to_string().contains("Invalid query"));
+        assert!(CustomRuleSet::load(&dir.path().join("missing"), &PluginRegistry::default()).unwrap().is_empty());
+    }
+}
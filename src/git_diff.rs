@@ -0,0 +1,121 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::process::Command;
+use anyhow::Context;
+use crate::rules::AnalysisResult;
+use crate::rules::diff::DiffAnalysis;
+
+/// Limits a whole-repository analysis to the files a branch changed, for
+/// pull request review (`--since <git-ref>`).
+pub struct GitDiff;
+
+impl GitDiff {
+    /// Files under `root` changed since the merge base of `base_ref` and
+    /// `HEAD`, including uncommitted and untracked ones. Deleted files are
+    /// left out; paths are relative to `root`.
+    pub fn changed_files(root: &Path, base_ref: &str) -> anyhow::Result<BTreeSet<String>> {
+        let mut files = Self::git(root, &["diff", "--name-only", "--relative", "--diff-filter=d", "--merge-base", base_ref])?;
+        files.extend(Self::git(root, &["ls-files", "--others", "--exclude-standard"])?);
+        Ok(files)
+    }
+
+    /// Drops per-file findings outside `changed_files`. Naming violations are
+    /// judged against conventions detected across the whole repository, so
+    /// the ones left are where the changed code deviates from them.
+    pub fn scope(result: &mut AnalysisResult, base_ref: &str, changed_files: &BTreeSet<String>) {
+        let changed = |file: &String| changed_files.contains(file);
+
+        result.security.hardcoded_secrets.retain(|secret| changed(&secret.file));
+        result.dry.duplicated_blocks.retain(|block| changed(&block.file));
+        result.dry.clones.retain(|clone| changed(&clone.duplicate.file) || changed(&clone.original.file));
+        result.naming_violations.retain(|violation| changed(&violation.file));
+        result.custom_rules.findings.retain(|finding| changed(&finding.file));
+        result.security.unsafe_blocks.retain(|block| changed(&block.file));
+        result.error_handling.unwrap_usage.retain(|usage| changed(&usage.file));
+        result.error_handling.logging.stray_prints.retain(|print| changed(&print.file));
+        for findings in result.languages.values_mut() {
+            findings.error_handling.unwrap_usage.retain(|usage| changed(&usage.file));
+            findings.error_handling.logging.stray_prints.retain(|print| changed(&print.file));
+        }
+
+        result.diff = Some(DiffAnalysis {
+            base_ref: base_ref.to_string(),
+            changed_files: changed_files.iter().cloned().collect(),
+        });
+    }
+
+    fn git(root: &Path, args: &[&str]) -> anyhow::Result<BTreeSet<String>> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(args)
+            .output()
+            .context("Failed to run git")?;
+        if !output.status.success() {
+            anyhow::bail!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::ProjectAnalyzer;
+
+    fn git(root: &Path, args: &[&str]) {
+        let status = Command::new("git").arg("-C").arg(root).args(args).status().unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn test_since_limits_findings_to_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q", "-b", "main"]);
+        git(root, &["config", "user.email", "dev@example.com"]);
+        git(root, &["config", "user.name", "dev"]);
+
+        std::fs::write(root.join("models.py"), "def load_user():\n    pass\n\ndef save_user():\n    pass\n\ndef fetchAll():\n    pass\n").unwrap();
+        std::fs::write(root.join("views.py"), "def render_page():\n    pass\n\ndef render_list():\n    pass\n").unwrap();
+        git(root, &["add", "."]);
+        git(root, &["commit", "-q", "-m", "initial"]);
+
+        std::fs::write(root.join("views.py"), "def render_page():\n    pass\n\ndef renderDetail():\n    pass\n").unwrap();
+        std::fs::write(root.join("forms.py"), "def validate_form():\n    pass\n").unwrap();
+
+        let result = ProjectAnalyzer::new(root).with_since(Some("main".to_string())).analyze().unwrap();
+        let violations = &result.naming_violations.violations;
+
+        assert_eq!(result.diff.unwrap().changed_files, vec!["forms.py", "views.py"]);
+        // `fetchAll` in the unchanged models.py is not reported.
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].name, "renderDetail");
+        assert_eq!(violations[0].file, "views.py");
+        assert_eq!(violations[0].line, 4);
+    }
+
+    #[test]
+    fn test_since_keeps_repository_wide_verdicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q", "-b", "main"]);
+        git(root, &["config", "user.email", "dev@example.com"]);
+        git(root, &["config", "user.name", "dev"]);
+
+        std::fs::write(root.join("ffi.rs"), "pub fn read(ptr: *const u8) -> u8 {\n    log::info!(\"reading\");\n    dbg!(ptr);\n    unsafe { *ptr }\n}\n").unwrap();
+        git(root, &["add", "."]);
+        git(root, &["commit", "-q", "-m", "initial"]);
+        std::fs::write(root.join("lib.rs"), "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n").unwrap();
+
+        let result = ProjectAnalyzer::new(root).with_since(Some("main".to_string())).analyze().unwrap();
+        assert!(result.security.unsafe_blocks.is_empty());
+        assert_eq!(result.security.memory_safety, "1 `unsafe` block(s) in 1 file(s)");
+        assert!(result.error_handling.logging.stray_prints.is_empty());
+        assert_eq!(result.error_handling.logging_consistency, "Mixed: log (100% of 1 log calls), 0% structured, 1 stray print call");
+    }
+}
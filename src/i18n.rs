@@ -0,0 +1,274 @@
+/// Language used for generated prose: report context text and LLM insights.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Locale {
+    /// English
+    #[default]
+    En,
+    /// Bahasa Indonesia
+    Id,
+}
+
+impl Locale {
+    /// English name of the language, used when instructing an LLM.
+    pub fn language_name(&self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Id => "Indonesian",
+        }
+    }
+}
+
+/// Looks up a catalog message. Keys are `<language>.<aspect>.<finding>.<kind>`
+/// where kind is `context` or `recommendation`. Unknown keys return the key
+/// itself so a missing entry is visible in the report rather than silent.
+pub fn message(locale: Locale, key: &'static str) -> &'static str {
+    match translations(key) {
+        Some((en, id)) => match locale {
+            Locale::En => en,
+            Locale::Id => id,
+        },
+        None => key,
+    }
+}
+
+/// (English, Indonesian) pairs, kept side by side so translations stay in sync.
+fn translations(key: &str) -> Option<(&'static str, &'static str)> {
+    let pair = match key {
+        "go.error_handling.err_check.context" => (
+            "This is the idiomatic Go pattern for making sure failures are handled explicitly.",
+            "Pola ini adalah standar idiomatis dalam bahasa Go untuk memastikan kegagalan ditangani secara eksplisit.",
+        ),
+        "go.error_handling.err_check.recommendation" => (
+            "Wrap errors with `%w` in `fmt.Errorf` to keep the error chain and context when returning them to the caller.",
+            "Pastikan untuk membungkus error (*error wrapping*) menggunakan `%w` pada `fmt.Errorf` untuk mempertahankan *stack trace* atau konteks error saat dikembalikan ke pemanggil.",
+        ),
+        "go.error_handling.wrapping.context" => (
+            "Errors are already wrapped with `%w`, so callers can inspect the chain with `errors.Is` and `errors.As`.",
+            "Error sudah dibungkus dengan `%w`, sehingga pemanggil dapat memeriksa rantai error dengan `errors.Is` dan `errors.As`.",
+        ),
+        "go.error_handling.inspect.recommendation" => (
+            "Compare against sentinel errors with `errors.Is` instead of `==`, which misses wrapped errors.",
+            "Bandingkan sentinel error dengan `errors.Is` daripada `==`, yang tidak mengenali error yang sudah dibungkus.",
+        ),
+        "go.error_handling.pkg_errors.recommendation" => (
+            "`github.com/pkg/errors` is archived; the standard library has supported wrapping since Go 1.13.",
+            "`github.com/pkg/errors` sudah diarsipkan; library standar mendukung error wrapping sejak Go 1.13.",
+        ),
+        "go.error_handling.discarded.recommendation" => (
+            "Handle errors assigned to `_`, or leave a comment explaining why they can be ignored.",
+            "Tangani error yang di-assign ke `_`, atau beri komentar yang menjelaskan mengapa error tersebut boleh diabaikan.",
+        ),
+        "go.di.constructor.context" => (
+            "Factory functions (`NewXXX`) for struct initialization are the common Go way to support dependency injection.",
+            "Menggunakan factory function (`NewXXX`) untuk inisialisasi struct adalah pola umum di Go untuk mendukung dependency injection.",
+        ),
+        "go.di.constructor.recommendation" => (
+            "Consider accepting interfaces instead of concrete structs in constructors to improve *testability* and modularity.",
+            "Pertimbangkan untuk menerima interface daripada struct konkret dalam constructor untuk meningkatkan *testability* dan modularitas.",
+        ),
+        "go.testing.gomock.context" => (
+            "Using `gomock` indicates a mature testing culture built on generated mock objects.",
+            "Penggunaan `gomock` menunjukkan budaya testing yang matang dengan penggunaan mock objects yang tergenerasi.",
+        ),
+        "go.testing.gomock.recommendation" => (
+            "Regenerate mocks with `mockgen` whenever an interface changes.",
+            "Pastikan mock diupdate setiap kali ada perubahan pada interface menggunakan `mockgen`.",
+        ),
+        "go.config.properties.context" => (
+            "Using `properties.yaml` shows a structured configuration approach similar to Spring Boot.",
+            "Penggunaan `properties.yaml` menunjukkan adaptasi pola konfigurasi terstruktur mirip Spring Boot.",
+        ),
+        "go.config.properties.recommendation" => (
+            "Load configuration into validated structs to guarantee type safety at runtime.",
+            "Pastikan konfigurasi di-load ke dalam struct yang ter-validate untuk menjamin type safety saat runtime.",
+        ),
+        "go.design_patterns.factory.context" => (
+            "The Factory pattern is used to encapsulate initialization of complex objects.",
+            "Factory pattern digunakan untuk enkapsulasi inisialisasi objek kompleks.",
+        ),
+        "go.design_patterns.singleton.context" => (
+            "Singletons are used for global access to a single resource (such as a DB connection).",
+            "Singleton digunakan untuk akses global ke resource tunggal (seperti DB connection).",
+        ),
+        "go.design_patterns.singleton.recommendation" => (
+            "Be careful with Singletons in parallel tests; consider dependency injection as an alternative.",
+            "Hati-hati dengan Singleton dalam pengujian paralel; pertimbangkan dependency injection sebagai alternatif.",
+        ),
+        "go.design_patterns.strategy.context" => (
+            "The Strategy pattern is implemented through interfaces for algorithm flexibility.",
+            "Strategy pattern diimplementasikan melalui interfaces untuk fleksibilitas algoritma.",
+        ),
+        "go.naming.pascal.context" => (
+            "In Go, PascalCase exports a symbol so it can be used from other packages.",
+            "PascalCase di Go digunakan untuk mengekspor (export) simbol agar bisa diakses dari package lain.",
+        ),
+        "go.naming.pascal.recommendation" => (
+            "Use `camelCase` for internal (unexported) variables and functions to keep packages encapsulated.",
+            "Gunakan `camelCase` untuk internal (unexported) variabel dan fungsi guna menjaga enkapsulasi package.",
+        ),
+        "go.dry.context" => (
+            "The DRY (Don't Repeat Yourself) principle is key to keeping Go code maintainable.",
+            "Prinsip DRY (Don't Repeat Yourself) sangat penting untuk menjaga kemudahan pemeliharaan kode Go.",
+        ),
+        "go.dry.recommendation" => (
+            "Extract repeated logic into utility or helper functions, especially when it is used across several packages.",
+            "Gunakan fungsi utilitas atau helper untuk logika yang berulang, terutama jika logika tersebut digunakan di beberapa package.",
+        ),
+        "python.naming.snake.context" => (
+            "Python follows PEP 8, which recommends `snake_case` for variables and functions.",
+            "Python mengikuti PEP 8 yang merekomendasikan `snake_case` untuk variabel dan fungsi.",
+        ),
+        "python.naming.snake.recommendation" => (
+            "Consistently reserve `PascalCase` for class names.",
+            "Pastikan untuk konsisten menggunakan `PascalCase` hanya untuk nama Class.",
+        ),
+        "python.error_handling.context" => (
+            "Python relies on EAFP (*Easier to Ask for Forgiveness than Permission*) using `try-except` blocks.",
+            "Python mengandalkan EAFP (*Easier to Ask for Forgiveness than Permission*) menggunakan blok `try-except`.",
+        ),
+        "python.error_handling.recommendation" => (
+            "Catch specific exceptions instead of the generic `Exception` to avoid handling errors unintentionally.",
+            "Gunakan exception yang spesifik daripada menangkap `Exception` umum untuk menghindari penanganan error yang tidak disengaja.",
+        ),
+        "python.error_handling.custom.context" => (
+            "The project defines its own exception hierarchy; raise those types rather than built-in ones for domain failures.",
+            "Proyek ini mendefinisikan hierarki exception sendiri; gunakan tipe tersebut daripada exception bawaan untuk kegagalan domain.",
+        ),
+        "python.error_handling.chaining.recommendation" => (
+            "When translating an exception, use `raise NewError(...) from err` to keep the original traceback.",
+            "Saat menerjemahkan exception, gunakan `raise NewError(...) from err` agar traceback asli tetap tersimpan.",
+        ),
+        "python.di.depends.context" => (
+            "FastAPI resolves dependencies declared with `Depends(...)` in route signatures.",
+            "FastAPI me-resolve dependency yang dideklarasikan dengan `Depends(...)` pada signature route.",
+        ),
+        "python.di.depends.recommendation" => (
+            "Replace dependencies in tests through `app.dependency_overrides` instead of patching modules.",
+            "Ganti dependency saat testing melalui `app.dependency_overrides` daripada mem-patch modul.",
+        ),
+        "python.di.container.context" => (
+            "Dependencies are wired through `dependency-injector` containers and providers.",
+            "Dependency dihubungkan melalui container dan provider dari `dependency-injector`.",
+        ),
+        "python.di.constructor.context" => (
+            "Classes receive their collaborators through `__init__` parameters, which keeps them easy to substitute in tests.",
+            "Class menerima dependency melalui parameter `__init__`, sehingga mudah diganti saat testing.",
+        ),
+        "python.testing.pytest.context" => (
+            "Tests are written for pytest: plain `test_*` functions with bare `assert` statements.",
+            "Test ditulis untuk pytest: fungsi `test_*` biasa dengan pernyataan `assert`.",
+        ),
+        "python.testing.pytest.recommendation" => (
+            "Share setup through fixtures in `conftest.py` rather than helper base classes.",
+            "Bagikan setup melalui fixture di `conftest.py` daripada base class helper.",
+        ),
+        "python.testing.unittest.context" => (
+            "Tests subclass `unittest.TestCase` and use the `self.assert*` methods.",
+            "Test merupakan turunan `unittest.TestCase` dan menggunakan method `self.assert*`.",
+        ),
+        "python.testing.unittest.recommendation" => (
+            "Keep new tests in `TestCase` classes so they match the existing suite and its `setUp` hooks.",
+            "Tempatkan test baru di class `TestCase` agar konsisten dengan suite yang ada beserta hook `setUp`-nya.",
+        ),
+        "python.design_patterns.abc.context" => (
+            "Interfaces are expressed as abstract base classes or protocols that implementations must satisfy.",
+            "Interface diekspresikan sebagai abstract base class atau protocol yang harus dipenuhi implementasinya.",
+        ),
+        "python.design_patterns.decorator.context" => (
+            "Cross-cutting behaviour is added with decorators built on `functools.wraps`.",
+            "Perilaku lintas fungsi ditambahkan dengan decorator yang dibangun di atas `functools.wraps`.",
+        ),
+        "python.design_patterns.singleton.context" => (
+            "Shared resources are created lazily and cached in module globals.",
+            "Resource bersama dibuat secara lazy dan disimpan di variabel global modul.",
+        ),
+        "python.design_patterns.singleton.recommendation" => (
+            "Provide a way to reset or inject these globals so tests do not leak state into each other.",
+            "Sediakan cara untuk me-reset atau meng-inject global tersebut agar state tidak bocor antar test.",
+        ),
+        "rust.error_handling.propagation.context" => (
+            "Errors are propagated to the caller with the `?` operator.",
+            "Error diteruskan ke pemanggil dengan operator `?`.",
+        ),
+        "rust.error_handling.crates.context" => (
+            "Errors are built with `thiserror` and/or `anyhow`: typed enums where callers match on variants, `anyhow` with `.context(...)` where they only report.",
+            "Error dibangun dengan `thiserror` dan/atau `anyhow`: enum bertipe saat pemanggil mencocokkan varian, `anyhow` dengan `.context(...)` saat error hanya dilaporkan.",
+        ),
+        "rust.error_handling.unwrap.recommendation" => (
+            "Outside tests, replace `unwrap()` with `?` or an `expect()` that states the invariant being relied on.",
+            "Di luar test, ganti `unwrap()` dengan `?` atau `expect()` yang menjelaskan invarian yang diandalkan.",
+        ),
+        "rust.di.traits.context" => (
+            "Dependencies are abstracted behind traits: trait objects (`dyn Trait`) where implementations vary at runtime, generic bounds for static dispatch.",
+            "Dependency diabstraksikan melalui trait: trait object (`dyn Trait`) jika implementasi berubah saat runtime, generic bound untuk static dispatch.",
+        ),
+        "js.naming.camel.context" => (
+            "Standard JavaScript/TypeScript uses `camelCase` for variables and functions.",
+            "JavaScript/TypeScript standar menggunakan `camelCase` untuk variabel dan fungsi.",
+        ),
+        "js.naming.camel.recommendation" => (
+            "Use `PascalCase` for classes and interfaces, and `UPPER_SNAKE_CASE` for global constants.",
+            "Gunakan `PascalCase` untuk Class dan Interfaces, serta `UPPER_SNAKE_CASE` untuk konstanta global.",
+        ),
+        "js.di.context" => (
+            "In the JS/TS ecosystem, constructor injection is common, especially with frameworks such as NestJS or Inversify.",
+            "Di ekosistem JS/TS, Constructor Injection sering digunakan terutama dengan framework seperti NestJS atau Inversify.",
+        ),
+        "js.di.recommendation" => (
+            "Use TypeScript interfaces to decouple consumers from providers so they are easy to mock in unit tests.",
+            "Manfaatkan TypeScript Interfaces untuk decoupling antara consumer dan provider agar lebih mudah di-mock saat unit testing.",
+        ),
+        "js.error_handling.result.context" => (
+            "Failures are returned as values through a Result/Either type instead of being thrown.",
+            "Kegagalan dikembalikan sebagai nilai melalui tipe Result/Either, bukan dilempar (throw).",
+        ),
+        "js.error_handling.result.recommendation" => (
+            "Return an error result from new code paths instead of throwing, so callers keep handling failures explicitly.",
+            "Kembalikan hasil error pada alur kode baru daripada melempar exception, agar pemanggil tetap menangani kegagalan secara eksplisit.",
+        ),
+        "js.error_handling.custom.context" => (
+            "Domain failures are modelled as `Error` subclasses and told apart with `instanceof`.",
+            "Kegagalan domain dimodelkan sebagai turunan `Error` dan dibedakan dengan `instanceof`.",
+        ),
+        "js.error_handling.async.recommendation" => (
+            "Prefer `try/catch` around `await` over `.catch()` chains so async failures are handled in one style.",
+            "Utamakan `try/catch` di sekitar `await` daripada rantai `.catch()` agar kegagalan async ditangani dengan satu gaya.",
+        ),
+        "js.testing.module_mock.context" => (
+            "Modules are replaced with `jest.mock`/`vi.mock`, which is hoisted above the imports of the test file.",
+            "Modul diganti dengan `jest.mock`/`vi.mock`, yang di-hoist ke atas import pada file test.",
+        ),
+        "js.testing.module_mock.recommendation" => (
+            "Enable `clearMocks` or `restoreMocks` so mock state does not leak between tests.",
+            "Aktifkan `clearMocks` atau `restoreMocks` agar state mock tidak bocor antar test.",
+        ),
+        "js.testing.sinon.recommendation" => (
+            "Call `sinon.restore()` in `afterEach` so stubs do not leak between tests.",
+            "Panggil `sinon.restore()` di `afterEach` agar stub tidak bocor antar test.",
+        ),
+        "logging.mixed.recommendation" => (
+            "Log through the dominant logger in new code so output stays in one format.",
+            "Gunakan logger yang dominan pada kode baru agar format output tetap seragam.",
+        ),
+        "logging.stray_prints.recommendation" => (
+            "Replace stray print statements in non-test code with log calls.",
+            "Ganti pernyataan print yang tersisa di kode non-test dengan pemanggilan logger.",
+        ),
+        _ => return None,
+    };
+    Some(pair)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_per_locale() {
+        let en = message(Locale::En, "go.dry.context");
+        let id = message(Locale::Id, "go.dry.context");
+        assert!(en.contains("maintainable"));
+        assert!(id.contains("pemeliharaan"));
+        assert_eq!(message(Locale::En, "missing.key"), "missing.key");
+    }
+}
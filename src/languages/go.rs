@@ -1,33 +1,197 @@
 use crate::languages::LanguageAnalyzer;
 use crate::rules::AnalysisResult;
-use crate::rules::naming::{NamingConvention, Casing};
-use tree_sitter::Query;
+use crate::rules::naming::IdentifierKind;
+use crate::rules::dry::DuplicatedBlock;
+use std::sync::LazyLock;
+use tree_sitter::{Language, Query};
 use streaming_iterator::StreamingIterator;
 
+const TECH_STACK_QUERY: &str = r#"
+    (import_spec path: (interpreted_string_literal) @import_path)
+"#;
+
+const TESTING_QUERY: &str = r#"
+    (import_spec path: (interpreted_string_literal) @import_path)
+    (call_expression
+        function: (selector_expression
+            operand: (identifier) @pkg_name
+            field: (field_identifier) @method_name
+        )
+        (#match? @pkg_name "gomock")
+    )
+"#;
+
+const DESIGN_PATTERNS_QUERY: &str = r#"
+    (function_declaration 
+        name: (identifier) @func_name
+        (#match? @func_name "^New[A-Z]")
+    ) @factory
+    
+    (function_declaration
+        name: (identifier) @singleton_name
+        (#match? @singleton_name "Get(Instance|Config|DB)")
+    ) @singleton
+    
+    (type_spec
+        name: (type_identifier) @type_name
+        type: (interface_type)
+    ) @interface
+"#;
+
+const NAMING_QUERY: &str = r#"
+    (function_declaration name: (identifier) @func_name)
+    (method_declaration name: (field_identifier) @method_name)
+    (type_spec name: (type_identifier) @type_name)
+    (var_spec name: (identifier) @var_name)
+    (short_var_declaration left: (expression_list (identifier) @var_name))
+    (type_spec
+        name: (type_identifier) @interface_name
+        type: (interface_type)
+    )
+"#;
+
+const ERROR_HANDLING_QUERY: &str = r#"
+    (if_statement 
+        condition: (binary_expression 
+            left: (identifier) @err_name 
+            operator: "!=" 
+            right: (nil)
+        )
+    ) @error_check
+    
+    (call_expression
+        function: (identifier) @func_name
+        (#match? @func_name "panic")
+    ) @panic_call
+
+    (call_expression
+        function: (selector_expression operand: (identifier) @pkg field: (field_identifier) @func)
+        (#eq? @pkg "fmt")
+        (#eq? @func "Errorf")
+    ) @errorf
+
+    (call_expression
+        function: (selector_expression operand: (identifier) @pkg field: (field_identifier) @inspect)
+        (#eq? @pkg "errors")
+        (#match? @inspect "^(Is|As)$")
+    )
+
+    (binary_expression
+        left: (identifier)
+        operator: ["==" "!="]
+        right: (identifier) @sentinel_comparison
+        (#match? @sentinel_comparison "^Err[A-Z]")
+    )
+
+    (var_spec
+        name: (identifier) @sentinel
+        value: (expression_list
+            (call_expression function: (selector_expression operand: (identifier) @pkg field: (field_identifier) @func)))
+        (#match? @sentinel "^[Ee]rr[A-Z]")
+        (#eq? @pkg "errors")
+        (#eq? @func "New")
+    )
+
+    (method_declaration
+        name: (field_identifier) @method
+        parameters: (parameter_list)
+        result: (type_identifier) @result
+        (#eq? @method "Error")
+        (#eq? @result "string")
+    ) @error_type
+
+    (import_spec path: (interpreted_string_literal) @import_path (#eq? @import_path "\"github.com/pkg/errors\""))
+
+    (assignment_statement
+        left: (expression_list . (identifier) @blank .)
+        right: (expression_list (call_expression))
+        (#eq? @blank "_")
+    ) @discarded
+"#;
+
+const LOGGING_QUERY: &str = r#"
+    (import_spec path: (interpreted_string_literal) @import_path)
+    (call_expression
+        function: (selector_expression operand: (_) @receiver field: (field_identifier) @level)
+        (#match? @level "^(Debug|Info|Warn|Error|Fatal|Panic|Print|Trace)(f|w|ln)?$")
+    )
+    (call_expression
+        function: (selector_expression operand: (identifier) @pkg field: (field_identifier) @print)
+        (#eq? @pkg "fmt")
+        (#match? @print "^Print(f|ln)?$")
+    ) @stray_print
+    (call_expression function: (identifier) @builtin (#match? @builtin "^print(ln)?$")) @stray_print
+"#;
+
+/// Import path to logging library, for the packages `log` calls go through.
+const LOGGERS: &[(&str, &str)] = &[
+    ("log", "log"),
+    ("log/slog", "slog"),
+    ("go.uber.org/zap", "zap"),
+    ("github.com/sirupsen/logrus", "logrus"),
+    ("github.com/rs/zerolog", "zerolog"),
+    ("github.com/rs/zerolog/log", "zerolog"),
+];
+
+const DI_QUERY: &str = r#"
+    (function_declaration 
+        name: (identifier) @func_name
+        (#match? @func_name "^New[A-Z]")
+    )
+"#;
+
+const DRY_QUERY: &str = r#"
+    (interpreted_string_literal) @string
+"#;
+
+pub(crate) struct GoQueries {
+    tech_stack: Query,
+    testing: Query,
+    design_patterns: Query,
+    naming: Query,
+    error_handling: Query,
+    di: Query,
+    logging: Query,
+    dry: Query,
+}
+
+impl GoQueries {
+    pub(crate) fn new(lang: &Language) -> Self {
+        Self {
+            tech_stack: Query::new(lang, TECH_STACK_QUERY).unwrap(),
+            testing: Query::new(lang, TESTING_QUERY).unwrap(),
+            design_patterns: Query::new(lang, DESIGN_PATTERNS_QUERY).unwrap(),
+            naming: Query::new(lang, NAMING_QUERY).unwrap(),
+            error_handling: Query::new(lang, ERROR_HANDLING_QUERY).unwrap(),
+            di: Query::new(lang, DI_QUERY).unwrap(),
+            logging: Query::new(lang, LOGGING_QUERY).unwrap(),
+            dry: Query::new(lang, DRY_QUERY).unwrap(),
+        }
+    }
+}
+
+static QUERIES: LazyLock<GoQueries> = LazyLock::new(|| GoQueries::new(&tree_sitter_go::LANGUAGE.into()));
+
 pub struct GoAnalyzer;
 
 impl LanguageAnalyzer for GoAnalyzer {
     fn analyze(&self, content: &str, tree: &tree_sitter::Tree, result: &mut AnalysisResult) {
-        let ts_lang = tree_sitter_go::LANGUAGE.into();
-        
-        self.analyze_naming(content, tree, &ts_lang, result);
-        self.analyze_error_handling(content, tree, &ts_lang, result);
-        self.analyze_di(content, tree, &ts_lang, result);
-        self.analyze_design_patterns(content, tree, &ts_lang, result);
-        self.analyze_testing(content, tree, &ts_lang, result);
-        self.analyze_tech_stack(content, tree, &ts_lang, result);
-        self.analyze_dry(content, tree, &ts_lang, result);
+        let queries = &*QUERIES;
+        self.analyze_naming(content, tree, &queries.naming, result);
+        self.analyze_error_handling(content, tree, &queries.error_handling, result);
+        self.analyze_di(content, tree, &queries.di, result);
+        self.analyze_logging(content, tree, &queries.logging, result);
+        self.analyze_design_patterns(content, tree, &queries.design_patterns, result);
+        self.analyze_testing(content, tree, &queries.testing, result);
+        self.analyze_tech_stack(content, tree, &queries.tech_stack, result);
+        self.analyze_dry(content, tree, &queries.dry, result);
     }
 }
 
 impl GoAnalyzer {
-    fn analyze_tech_stack(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (import_spec path: (interpreted_string_literal) @import_path)
-        "#;
-        let query = Query::new(lang, query_str).unwrap();
+    fn analyze_tech_stack(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
 
         while let Some(m) = matches.next() {
             for capture in m.captures {
@@ -47,32 +211,15 @@ impl GoAnalyzer {
                 };
 
                 if let Some(cat) = category {
-                    match cat {
-                        "framework" => if !result.tech_stack.frameworks.contains(&name.to_string()) { result.tech_stack.frameworks.push(name.to_string()) },
-                        "database" => if !result.tech_stack.databases.contains(&name.to_string()) { result.tech_stack.databases.push(name.to_string()) },
-                        "library" => if !result.tech_stack.libraries.contains(&name.to_string()) { result.tech_stack.libraries.push(name.to_string()) },
-                        _ => {}
-                    }
+                    result.tech_stack.add(cat, name);
                 }
             }
         }
     }
 
-    fn analyze_testing(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (import_spec path: (interpreted_string_literal) @import_path)
-            (call_expression
-                function: (selector_expression
-                    operand: (identifier) @pkg_name
-                    field: (field_identifier) @method_name
-                )
-                (#match? @pkg_name "gomock")
-            )
-        "#;
-        
-        let query = Query::new(lang, query_str).unwrap();
+    fn analyze_testing(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
 
         while let Some(match_) = matches.next() {
             for capture in match_.captures {
@@ -90,27 +237,9 @@ impl GoAnalyzer {
         }
     }
 
-    fn analyze_design_patterns(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (function_declaration 
-                name: (identifier) @func_name
-                (#match? @func_name "^New[A-Z]")
-            ) @factory
-            
-            (function_declaration
-                name: (identifier) @singleton_name
-                (#match? @singleton_name "Get(Instance|Config|DB)")
-            ) @singleton
-            
-            (type_spec
-                name: (type_identifier) @type_name
-                type: (interface_type)
-            ) @interface
-        "#;
-
-        let query = Query::new(lang, query_str).unwrap();
+    fn analyze_design_patterns(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
 
         while let Some(match_) = matches.next() {
             for capture in match_.captures {
@@ -129,110 +258,142 @@ impl GoAnalyzer {
         }
     }
 
-    fn analyze_naming(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (function_declaration name: (identifier) @func_name)
-            (method_declaration name: (field_identifier) @method_name)
-            (type_spec name: (type_identifier) @type_name)
-            (var_spec name: (identifier) @var_name)
-            (short_var_declaration left: (expression_list (identifier) @var_name))
-            (type_spec
-                name: (type_identifier) @interface_name
-                type: (interface_type)
-            )
-        "#;
-        
-        let query = Query::new(lang, query_str).unwrap();
+    fn analyze_naming(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
 
         while let Some(match_) = matches.next() {
             for capture in match_.captures {
                 let node = capture.node;
                 let name = &content[node.start_byte()..node.end_byte()];
-                let casing = NamingConvention::detect_casing(name);
+                let line = node.start_position().row + 1;
                 
                 let capture_name = query.capture_names()[capture.index as usize];
                 match capture_name {
-                    "func_name" | "method_name" => {
-                        if result.naming.function_casing == Casing::Unknown {
-                            result.naming.function_casing = casing;
-                        }
-                    },
-                    "type_name" => {
-                        if result.naming.class_struct_naming == Casing::Unknown {
-                            result.naming.class_struct_naming = casing;
-                        }
-                    },
-                    "var_name" => {
-                        if result.naming.variable_casing == Casing::Unknown {
-                            result.naming.variable_casing = casing;
-                        }
-                    },
-                    "interface_name" => {
-                        if name.starts_with('I') && name.chars().nth(1).is_some_and(|c| c.is_uppercase()) {
-                            result.naming.interface_prefix = Some("I".to_string());
-                        }
-                    },
+                    "func_name" | "method_name" => result.naming.record_identifier(IdentifierKind::Function, name, line),
+                    "type_name" => result.naming.record_identifier(IdentifierKind::Type, name, line),
+                    "var_name" => result.naming.record_identifier(IdentifierKind::Variable, name, line),
+                    "interface_name" => result.naming.record_interface(name),
                     _ => {}
                 }
             }
         }
     }
 
-    fn analyze_error_handling(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (if_statement 
-                condition: (binary_expression 
-                    left: (identifier) @err_name 
-                    operator: "!=" 
-                    right: (nil)
-                )
-            ) @error_check
-            
-            (call_expression
-                function: (identifier) @func_name
-                (#match? @func_name "panic")
-            ) @panic_call
-        "#;
-        
-        let query = Query::new(lang, query_str).unwrap();
+    fn analyze_error_handling(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
 
         while let Some(m) = matches.next() {
             for capture in m.captures {
+                let text = &content[capture.node.start_byte()..capture.node.end_byte()];
                 let capture_name = query.capture_names()[capture.index as usize];
-                match capture_name {
-                    "error_check" => {
-                        let pattern = "if err != nil".to_string();
-                        if !result.error_handling.failure_patterns.contains(&pattern) {
-                            result.error_handling.failure_patterns.push(pattern);
-                        }
-                    },
-                    "panic_call" => {
-                        let pattern = "panic()".to_string();
-                        if !result.error_handling.failure_patterns.contains(&pattern) {
-                            result.error_handling.failure_patterns.push(pattern);
-                        }
+                let pattern = match capture_name {
+                    "error_check" => "if err != nil",
+                    "panic_call" => "panic()",
+                    "errorf" => match Self::errorf_verb(capture.node, content) {
+                        Some(pattern) => pattern,
+                        None => continue,
                     },
-                    _ => {}
-                }
+                    "inspect" if text == "Is" => "errors.Is",
+                    "inspect" => "errors.As",
+                    "sentinel_comparison" => "Direct comparison (err == ErrX)",
+                    "sentinel" => "Sentinel errors (var Err... = errors.New)",
+                    "error_type" => "Custom error types (Error() string)",
+                    "import_path" => "pkg/errors",
+                    "discarded" => "Discarded errors (_ = f())",
+                    _ => continue,
+                };
+                result.error_handling.count(pattern);
             }
         }
     }
 
-    fn analyze_di(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (function_declaration 
-                name: (identifier) @func_name
-                (#match? @func_name "^New[A-Z]")
-            )
-        "#;
-        
-        let query = Query::new(lang, query_str).unwrap();
+    /// Classifies a `fmt.Errorf` call that includes an error among its
+    /// arguments by whether it wraps it (`%w`) or only formats its text.
+    /// Calls that create a fresh error return `None`.
+    fn errorf_verb(call: tree_sitter::Node, content: &str) -> Option<&'static str> {
+        let arguments = call.child_by_field_name("arguments")?;
+        let mut walker = arguments.walk();
+        let mut arguments = arguments.named_children(&mut walker);
+        let format = arguments.next()?;
+        let format = &content[format.start_byte()..format.end_byte()];
+        let formats_error = arguments.any(|argument| {
+            argument.kind() == "identifier" && content[argument.start_byte()..argument.end_byte()].to_lowercase().ends_with("err")
+        });
+
+        if format.contains("%w") {
+            Some("fmt.Errorf with %w")
+        } else if formats_error {
+            Some("fmt.Errorf with %v")
+        } else {
+            None
+        }
+    }
+
+    fn analyze_logging(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let text = |node: tree_sitter::Node| &content[node.start_byte()..node.end_byte()];
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut captures = cursor.captures(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        // Package name each import is referred to by, and the logging library
+        // behind it if there is one.
+        let mut packages: Vec<(&str, Option<&str>)> = Vec::new();
+        let mut calls = Vec::new();
+        while let Some(m) = matches.next() {
+            let capture = |name: &str| m.captures.iter()
+                .find(|c| query.capture_names()[c.index as usize] == name)
+                .map(|c| c.node);
+            if let Some(path) = capture("import_path") {
+                let import = text(path).trim_matches('"');
+                let package = path.parent()
+                    .and_then(|spec| spec.child_by_field_name("name"))
+                    .map(text)
+                    .unwrap_or_else(|| import.rsplit('/').next().unwrap_or(import));
+                let logger = LOGGERS.iter().find(|(path, _)| *path == import).map(|(_, logger)| *logger);
+                packages.push((package, logger));
+            } else if let Some(call) = capture("stray_print") {
+                let function = call.child_by_field_name("function").map(text).unwrap_or_default();
+                result.error_handling.logging.record_stray_print(function, call.start_position().row + 1);
+            } else if let (Some(receiver), Some(level)) = (capture("receiver"), capture("level")) {
+                calls.push((receiver, text(level)));
+            }
+        }
+
+        let mut file_loggers: Vec<&str> = packages.iter().filter_map(|(_, logger)| *logger).collect();
+        file_loggers.sort_unstable();
+        file_loggers.dedup();
+        // A logger held in a variable is rarely a `*log.Logger` when the file
+        // also imports a structured one.
+        if file_loggers.len() > 1 {
+            file_loggers.retain(|logger| *logger != "log");
+        }
+        for (receiver, level) in calls {
+            // `logrus.WithField(...).Info` and `zap.L().Info` are rooted at the package.
+            let mut root = receiver;
+            while let Some(inner) = root.child_by_field_name("operand").or_else(|| root.child_by_field_name("function")) {
+                root = inner;
+            }
+            let logger = match packages.iter().find(|(package, _)| *package == text(root)) {
+                Some((_, logger)) => *logger,
+                // A logger held in a variable or field, e.g. `s.logger.Info`.
+                None if text(receiver).to_lowercase().contains("log") && file_loggers.len() == 1 => Some(file_loggers[0]),
+                None => None,
+            };
+            let Some(logger) = logger else { continue };
+
+            let structured = !level.ends_with('f') && !level.ends_with("ln") && match logger {
+                "log" => false,
+                "logrus" => text(receiver).contains("WithField") || level.ends_with('w'),
+                _ => true,
+            };
+            result.error_handling.logging.record_call(logger, structured);
+        }
+    }
+
+    fn analyze_di(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut captures = cursor.captures(query, tree.root_node(), content.as_bytes());
 
         while captures.next().is_some() {
             let pattern = "Constructor Injection (NewXXX)".to_string();
@@ -242,28 +403,28 @@ impl GoAnalyzer {
         }
     }
 
-    fn analyze_dry(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (interpreted_string_literal) @string
-        "#;
-        let query = Query::new(lang, query_str).unwrap();
+    fn analyze_dry(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
 
         let mut strings = std::collections::HashMap::new();
         while let Some(m) = matches.next() {
             for capture in m.captures {
                 let text = &content[capture.node.start_byte()..capture.node.end_byte()];
                 if text.len() > 10 { // Only track reasonably long strings
-                    *strings.entry(text).or_insert(0) += 1;
+                    strings.entry(text).or_insert((0, capture.node.start_position().row + 1)).0 += 1;
                 }
             }
         }
 
-        for (text, count) in strings {
+        for (text, (count, line)) in strings {
             if count > 1 {
-                result.dry.duplicated_blocks.push(format!("String literal repeated {} times: {}", count, text));
-                result.dry.duplication_score += (count - 1) as f64 * 0.1;
+                result.dry.duplicated_blocks.push(DuplicatedBlock {
+                    file: String::new(),
+                    line,
+                    description: format!("String literal repeated {} times: {}", count, text),
+                });
+                result.dry.repeated_literals += count - 1;
             }
         }
     }
@@ -273,6 +434,7 @@ impl GoAnalyzer {
 mod tests {
     use super::*;
     use crate::rules::AnalysisResult;
+    use crate::rules::naming::Casing;
     use tree_sitter::Parser;
 
     #[test]
@@ -324,6 +486,109 @@ mod tests {
         assert!(result.error_handling.failure_patterns.contains(&"if err != nil".to_string()));
     }
 
+    #[test]
+    fn test_go_error_wrapping_analysis() {
+        let content = r#"
+            package store
+
+            import "github.com/pkg/errors"
+
+            var ErrNotFound = errors.New("not found")
+
+            type QueryError struct{ Query string }
+
+            func (e *QueryError) Error() string { return e.Query }
+
+            func Load(id string) error {
+                _ = audit(id)
+                if err := fetch(id); err != nil {
+                    if errors.Is(err, ErrNotFound) || err == ErrNotFound {
+                        return fmt.Errorf("load %s: %w", id, err)
+                    }
+                    return fmt.Errorf("load %s: %v", id, err)
+                }
+                return fmt.Errorf("empty id")
+            }
+        "#;
+
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_go::LANGUAGE.into()).unwrap();
+        let tree = parser.parse(content, None).unwrap();
+        let mut result = AnalysisResult::default();
+        GoAnalyzer.analyze(content, &tree, &mut result);
+
+        let error_handling = &result.error_handling;
+        assert_eq!(error_handling.count_of("fmt.Errorf with %w"), 1);
+        assert_eq!(error_handling.count_of("fmt.Errorf with %v"), 1);
+        assert_eq!(error_handling.count_of("errors.Is"), 1);
+        assert_eq!(error_handling.count_of("Direct comparison (err == ErrX)"), 1);
+        assert_eq!(error_handling.count_of("Sentinel errors (var Err... = errors.New)"), 1);
+        assert_eq!(error_handling.count_of("Custom error types (Error() string)"), 1);
+        assert_eq!(error_handling.count_of("pkg/errors"), 1);
+        assert_eq!(error_handling.count_of("Discarded errors (_ = f())"), 1);
+    }
+
+    #[test]
+    fn test_go_logging_analysis() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("server.go"), r#"package server
+
+import (
+	"fmt"
+	"log"
+
+	"go.uber.org/zap"
+)
+
+func (s *Server) Start() error {
+	s.logger.Info("starting", zap.Int("port", s.port))
+	zap.L().Warn("deprecated flag", zap.String("flag", "x"))
+	log.Printf("listening on %d", s.port)
+	fmt.Println("debug: started")
+	return err.Error()
+}
+"#).unwrap();
+        std::fs::write(dir.path().join("server_test.go"), "package server
+
+import \"fmt\"
+
+func TestStart() {
+	fmt.Println(\"ok\")
+}
+").unwrap();
+        let result = crate::analyzer::ProjectAnalyzer::new(dir.path()).analyze().unwrap();
+
+        let logging = &result.error_handling.logging;
+        assert_eq!(logging.logger_calls.get("zap"), Some(&2));
+        assert_eq!(logging.logger_calls.get("log"), Some(&1));
+        assert_eq!(logging.stray_prints.len(), 1);
+        assert_eq!((logging.stray_prints[0].file.as_str(), logging.stray_prints[0].line), ("server.go", 14));
+        assert_eq!(result.error_handling.logging_consistency, "Mixed: zap (67% of 3 log calls; also log), 67% structured, 1 stray print call");
+    }
+
+    #[test]
+    fn test_go_held_logger_with_repeated_logger_imports() {
+        let content = r#"package server
+
+import (
+	"github.com/rs/zerolog"
+	stdlog "log"
+	"github.com/rs/zerolog/log"
+)
+
+func (s *Server) Start() {
+	s.logger.Info().Msg("starting")
+}
+"#;
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&tree_sitter_go::LANGUAGE.into()).unwrap();
+        let tree = parser.parse(content, None).unwrap();
+        let mut result = AnalysisResult::default();
+        GoAnalyzer.analyze(content, &tree, &mut result);
+
+        assert_eq!(result.error_handling.logging.logger_calls.get("zerolog"), Some(&1));
+    }
+
     #[test]
     fn test_go_dry_analysis() {
         let content = r#"
@@ -343,8 +608,9 @@ mod tests {
         let mut result = AnalysisResult::default();
         analyzer.analyze(content, &tree, &mut result);
         
-        assert!(result.dry.duplication_score > 0.0);
+        assert!(result.dry.repeated_literals > 0);
         assert!(!result.dry.duplicated_blocks.is_empty());
-        assert!(result.dry.duplicated_blocks[0].contains("this is a long repeated string"));
+        assert!(result.dry.duplicated_blocks[0].description.contains("this is a long repeated string"));
+        assert_eq!(result.dry.duplicated_blocks[0].line, 4);
     }
 }
@@ -0,0 +1,446 @@
+use crate::languages::LanguageAnalyzer;
+use crate::rules::AnalysisResult;
+use crate::rules::naming::IdentifierKind;
+use crate::rules::dry::DuplicatedBlock;
+use std::sync::LazyLock;
+use tree_sitter::{Language, Query};
+use streaming_iterator::StreamingIterator;
+
+const TECH_STACK_QUERY: &str = r#"
+    (import_declaration (scoped_identifier) @import_path)
+"#;
+
+const TESTING_QUERY: &str = r#"
+    (import_declaration (scoped_identifier) @import_path)
+    (marker_annotation name: (identifier) @annotation)
+    (class_declaration name: (identifier) @class_name)
+"#;
+
+const DESIGN_PATTERNS_QUERY: &str = r#"
+    (class_declaration
+        name: (identifier) @factory_name
+        (#match? @factory_name "Factory$")
+    ) @factory
+
+    (class_declaration
+        name: (identifier) @builder_name
+        (#match? @builder_name "Builder$")
+    ) @builder
+
+    (method_declaration
+        name: (identifier) @singleton_name
+        (#eq? @singleton_name "getInstance")
+    ) @singleton
+
+    (interface_declaration name: (identifier) @interface_name) @interface
+
+    (marker_annotation
+        name: (identifier) @lombok_builder
+        (#eq? @lombok_builder "Builder")
+    ) @builder
+"#;
+
+const NAMING_QUERY: &str = r#"
+    (method_declaration name: (identifier) @func_name)
+    (class_declaration name: (identifier) @type_name)
+    (interface_declaration name: (identifier) @type_name)
+    (enum_declaration name: (identifier) @type_name)
+    (record_declaration name: (identifier) @type_name)
+    (local_variable_declaration declarator: (variable_declarator name: (identifier) @var_name))
+    (interface_declaration name: (identifier) @interface_name)
+"#;
+
+const ERROR_HANDLING_QUERY: &str = r#"
+    (catch_clause (catch_formal_parameter (catch_type) @catch_type)) @catch
+    (try_with_resources_statement) @try_with_resources
+    (throws) @throws
+    (class_declaration
+        superclass: (superclass (type_identifier) @super_name)
+        (#match? @super_name "^(Exception|RuntimeException)$")
+    ) @custom_exception
+    (marker_annotation
+        name: (identifier) @advice
+        (#match? @advice "^(ControllerAdvice|RestControllerAdvice)$")
+    ) @controller_advice
+"#;
+
+const DI_QUERY: &str = r#"
+    (field_declaration (modifiers [
+        (marker_annotation name: (identifier) @field_annotation)
+        (annotation name: (identifier) @field_annotation)
+    ]))
+    (constructor_declaration (modifiers [
+        (marker_annotation name: (identifier) @constructor_annotation)
+        (annotation name: (identifier) @constructor_annotation)
+    ]))
+    (method_declaration (modifiers [
+        (marker_annotation name: (identifier) @method_annotation)
+        (annotation name: (identifier) @method_annotation)
+    ]))
+    (class_declaration (modifiers [
+        (marker_annotation name: (identifier) @class_annotation)
+        (annotation name: (identifier) @class_annotation)
+    ]))
+    (constructor_declaration parameters: (formal_parameters (formal_parameter))) @injecting_constructor
+"#;
+
+const DRY_QUERY: &str = r#"
+    (string_literal) @string
+"#;
+
+pub(crate) struct JavaQueries {
+    tech_stack: Query,
+    testing: Query,
+    design_patterns: Query,
+    naming: Query,
+    error_handling: Query,
+    di: Query,
+    dry: Query,
+}
+
+impl JavaQueries {
+    pub(crate) fn new(lang: &Language) -> Self {
+        Self {
+            tech_stack: Query::new(lang, TECH_STACK_QUERY).unwrap(),
+            testing: Query::new(lang, TESTING_QUERY).unwrap(),
+            design_patterns: Query::new(lang, DESIGN_PATTERNS_QUERY).unwrap(),
+            naming: Query::new(lang, NAMING_QUERY).unwrap(),
+            error_handling: Query::new(lang, ERROR_HANDLING_QUERY).unwrap(),
+            di: Query::new(lang, DI_QUERY).unwrap(),
+            dry: Query::new(lang, DRY_QUERY).unwrap(),
+        }
+    }
+}
+
+static QUERIES: LazyLock<JavaQueries> = LazyLock::new(|| JavaQueries::new(&tree_sitter_java::LANGUAGE.into()));
+
+pub struct JavaAnalyzer;
+
+/// Spring stereotypes whose constructors are used for dependency injection.
+const SPRING_STEREOTYPES: &[&str] = &["Service", "Component", "Repository", "Controller", "RestController", "Configuration"];
+
+impl LanguageAnalyzer for JavaAnalyzer {
+    fn analyze(&self, content: &str, tree: &tree_sitter::Tree, result: &mut AnalysisResult) {
+        let queries = &*QUERIES;
+        self.analyze_naming(content, tree, &queries.naming, result);
+        self.analyze_error_handling(content, tree, &queries.error_handling, result);
+        self.analyze_di(content, tree, &queries.di, result);
+        self.analyze_design_patterns(content, tree, &queries.design_patterns, result);
+        self.analyze_testing(content, tree, &queries.testing, result);
+        self.analyze_tech_stack(content, tree, &queries.tech_stack, result);
+        self.analyze_dry(content, tree, &queries.dry, result);
+    }
+}
+
+impl JavaAnalyzer {
+    fn analyze_tech_stack(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let node = capture.node;
+                let path = &content[node.start_byte()..node.end_byte()];
+
+                let (category, name) = match path {
+                    p if p.starts_with("org.springframework.boot") => (Some("framework"), "Spring Boot"),
+                    p if p.starts_with("org.springframework.data.jpa") => (Some("database"), "Spring Data JPA"),
+                    p if p.starts_with("org.springframework") => (Some("framework"), "Spring"),
+                    p if p.starts_with("io.quarkus") => (Some("framework"), "Quarkus"),
+                    p if p.starts_with("io.micronaut") => (Some("framework"), "Micronaut"),
+                    p if p.starts_with("org.hibernate") => (Some("database"), "Hibernate"),
+                    p if p.starts_with("javax.persistence") || p.starts_with("jakarta.persistence") => (Some("database"), "JPA"),
+                    p if p.starts_with("org.mybatis") => (Some("database"), "MyBatis"),
+                    p if p.starts_with("lombok") => (Some("library"), "Lombok"),
+                    p if p.starts_with("com.fasterxml.jackson") => (Some("library"), "Jackson"),
+                    p if p.starts_with("org.slf4j") => (Some("library"), "SLF4J"),
+                    _ => (None, ""),
+                };
+
+                if let Some(cat) = category {
+                    result.tech_stack.add(cat, name);
+                }
+            }
+        }
+    }
+
+    fn analyze_testing(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+        let (mut junit4, mut junit5, mut assertj) = (false, false, false);
+
+        while let Some(match_) = matches.next() {
+            for capture in match_.captures {
+                let node = capture.node;
+                let text = &content[node.start_byte()..node.end_byte()];
+                let capture_name = query.capture_names()[capture.index as usize];
+
+                match capture_name {
+                    "import_path" if text.starts_with("org.junit.jupiter") => junit5 = true,
+                    "import_path" if text.starts_with("org.junit") => junit4 = true,
+                    "import_path" if text.starts_with("org.assertj") => assertj = true,
+                    "import_path" if text.starts_with("org.mockito") => {
+                        result.testing.mocking_strategy = "Mockito".to_string();
+                    },
+                    "annotation" if text == "MockBean" => {
+                        result.testing.mocking_strategy = "Mockito (@MockBean)".to_string();
+                    },
+                    "class_name" if text.ends_with("Test") || text.ends_with("Tests") => {
+                        result.testing.naming_pattern = "*Test classes".to_string();
+                    },
+                    "class_name" if text.ends_with("IT") => {
+                        result.testing.naming_pattern = "*IT integration test classes".to_string();
+                    },
+                    _ => {}
+                }
+            }
+        }
+
+        // Decided after all imports are seen, so the order they appear in
+        // doesn't matter: AssertJ replaces JUnit's assertions, and any Jupiter
+        // import means JUnit 5 even next to `org.junit` ones.
+        let style = if assertj {
+            "AssertJ"
+        } else if junit5 {
+            "JUnit 5"
+        } else if junit4 {
+            "JUnit 4"
+        } else {
+            return;
+        };
+        result.testing.assertion_style = style.to_string();
+    }
+
+    fn analyze_design_patterns(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        while let Some(match_) = matches.next() {
+            for capture in match_.captures {
+                let capture_name = query.capture_names()[capture.index as usize];
+                let pattern = match capture_name {
+                    "factory" => "Factory Pattern (*Factory)".to_string(),
+                    "builder" => "Builder Pattern".to_string(),
+                    "singleton" => "Potential Singleton (getInstance)".to_string(),
+                    "interface" => "Strategy Pattern (via Interfaces)".to_string(),
+                    _ => continue,
+                };
+
+                if !result.design_patterns.patterns.contains(&pattern) {
+                    result.design_patterns.patterns.push(pattern);
+                }
+            }
+        }
+    }
+
+    fn analyze_naming(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        while let Some(match_) = matches.next() {
+            for capture in match_.captures {
+                let node = capture.node;
+                let name = &content[node.start_byte()..node.end_byte()];
+                let line = node.start_position().row + 1;
+
+                let capture_name = query.capture_names()[capture.index as usize];
+                match capture_name {
+                    "func_name" => result.naming.record_identifier(IdentifierKind::Function, name, line),
+                    "type_name" => result.naming.record_identifier(IdentifierKind::Type, name, line),
+                    "var_name" => result.naming.record_identifier(IdentifierKind::Variable, name, line),
+                    "interface_name" => result.naming.record_interface(name),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn analyze_error_handling(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let capture_name = query.capture_names()[capture.index as usize];
+                let text = &content[capture.node.start_byte()..capture.node.end_byte()];
+                let pattern = match capture_name {
+                    "catch" => "try/catch",
+                    "catch_type" if text == "Exception" || text == "Throwable" => "Generic catch (Exception/Throwable)",
+                    "try_with_resources" => "try-with-resources",
+                    "throws" => "Checked exceptions (throws)",
+                    "custom_exception" => "Custom Exception Types",
+                    "controller_advice" => "Centralized handling (@ControllerAdvice)",
+                    _ => continue,
+                }.to_string();
+
+                if !result.error_handling.failure_patterns.contains(&pattern) {
+                    result.error_handling.failure_patterns.push(pattern);
+                }
+            }
+        }
+    }
+
+    fn analyze_di(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        let mut is_spring_bean = false;
+        let mut has_injecting_constructor = false;
+        let mut patterns = Vec::new();
+
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let capture_name = query.capture_names()[capture.index as usize];
+                let text = &content[capture.node.start_byte()..capture.node.end_byte()];
+                let is_injection = text == "Autowired" || text == "Inject";
+                match capture_name {
+                    "field_annotation" if is_injection => patterns.push(format!("Field Injection (@{})", text)),
+                    "constructor_annotation" if is_injection => patterns.push(format!("Constructor Injection (@{})", text)),
+                    "method_annotation" if is_injection => patterns.push(format!("Setter Injection (@{})", text)),
+                    "class_annotation" if text == "RequiredArgsConstructor" || text == "AllArgsConstructor" => {
+                        patterns.push(format!("Constructor Injection (Lombok @{})", text));
+                    },
+                    "class_annotation" if SPRING_STEREOTYPES.contains(&text) => is_spring_bean = true,
+                    "injecting_constructor" => has_injecting_constructor = true,
+                    _ => {}
+                }
+            }
+        }
+
+        if is_spring_bean && has_injecting_constructor {
+            patterns.push("Constructor Injection (Spring stereotype)".to_string());
+        }
+
+        for pattern in patterns {
+            if !result.di.injection_patterns.contains(&pattern) {
+                result.di.injection_patterns.push(pattern);
+            }
+        }
+    }
+
+    fn analyze_dry(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        let mut strings = std::collections::HashMap::new();
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let text = &content[capture.node.start_byte()..capture.node.end_byte()];
+                if text.len() > 10 {
+                    strings.entry(text).or_insert((0, capture.node.start_position().row + 1)).0 += 1;
+                }
+            }
+        }
+
+        for (text, (count, line)) in strings {
+            if count > 1 {
+                result.dry.duplicated_blocks.push(DuplicatedBlock {
+                    file: String::new(),
+                    line,
+                    description: format!("String literal repeated {} times: {}", count, text),
+                });
+                result.dry.repeated_literals += count - 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::AnalysisResult;
+    use crate::rules::naming::Casing;
+    use tree_sitter::Parser;
+
+    fn analyze(content: &str) -> AnalysisResult {
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_java::LANGUAGE.into()).unwrap();
+        let tree = parser.parse(content, None).unwrap();
+
+        let mut result = AnalysisResult::default();
+        JavaAnalyzer.analyze(content, &tree, &mut result);
+        result
+    }
+
+    #[test]
+    fn test_java_spring_service_analysis() {
+        let content = r#"
+package com.example.user;
+
+import org.springframework.stereotype.Service;
+import org.springframework.beans.factory.annotation.Autowired;
+import jakarta.persistence.EntityManager;
+
+@Service
+public class UserService {
+    @Autowired
+    private AuditLog auditLog;
+
+    private final UserRepository userRepository;
+
+    public UserService(UserRepository userRepository) {
+        this.userRepository = userRepository;
+    }
+
+    public User findUser(long userId) throws NotFoundException {
+        try {
+            User foundUser = userRepository.findById(userId);
+            return foundUser;
+        } catch (Exception e) {
+            throw new NotFoundException(e);
+        }
+    }
+}
+
+class NotFoundException extends RuntimeException {}
+"#;
+        let result = analyze(content);
+
+        assert_eq!(result.naming.function_casing, Casing::CamelCase);
+        assert_eq!(result.naming.class_struct_naming, Casing::PascalCase);
+        assert_eq!(result.naming.variable_casing, Casing::CamelCase);
+        assert!(result.tech_stack.frameworks.iter().any(|entry| *entry == "Spring"));
+        assert!(result.tech_stack.databases.iter().any(|entry| *entry == "JPA"));
+        assert!(result.di.injection_patterns.contains(&"Field Injection (@Autowired)".to_string()));
+        assert!(result.di.injection_patterns.contains(&"Constructor Injection (Spring stereotype)".to_string()));
+        for pattern in ["try/catch", "Generic catch (Exception/Throwable)", "Checked exceptions (throws)", "Custom Exception Types"] {
+            assert!(result.error_handling.failure_patterns.contains(&pattern.to_string()), "missing {}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_java_junit_mockito_detection() {
+        let content = r#"
+import org.junit.jupiter.api.Test;
+import org.mockito.Mock;
+
+class UserServiceTest {
+    @Mock
+    UserRepository userRepository;
+
+    @Test
+    void findsUser() {}
+}
+"#;
+        let result = analyze(content);
+
+        assert_eq!(result.testing.assertion_style, "JUnit 5");
+        assert_eq!(result.testing.mocking_strategy, "Mockito");
+        assert_eq!(result.testing.naming_pattern, "*Test classes");
+    }
+
+    #[test]
+    fn test_assertion_style_ignores_import_order() {
+        let cases = [
+            ("import org.junit.Assert;", "import org.junit.jupiter.api.Test;", "JUnit 5"),
+            ("import org.assertj.core.api.Assertions;", "import org.junit.jupiter.api.Test;", "AssertJ"),
+            ("import org.assertj.core.api.Assertions;", "import org.junit.Test;", "AssertJ"),
+        ];
+        for (first, second, expected) in cases {
+            for content in [format!("{}\n{}", first, second), format!("{}\n{}", second, first)] {
+                assert_eq!(analyze(&content).testing.assertion_style, expected, "{}", content);
+            }
+        }
+        assert_eq!(analyze("import org.junit.Test;").testing.assertion_style, "JUnit 4");
+    }
+}
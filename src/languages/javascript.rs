@@ -1,34 +1,175 @@
-use crate::languages::LanguageAnalyzer;
+use crate::languages::{LanguageAnalyzer, push_unique};
 use crate::rules::AnalysisResult;
-use crate::rules::naming::{NamingConvention, Casing};
-use tree_sitter::Query;
+use crate::rules::naming::IdentifierKind;
+use crate::rules::dry::DuplicatedBlock;
+use std::sync::LazyLock;
+use tree_sitter::{Language, Query};
 use streaming_iterator::StreamingIterator;
 
+const TECH_STACK_QUERY: &str = r#"
+    (import_statement source: (string) @import_source)
+    (call_expression
+        function: (identifier) @func_name
+        arguments: (arguments (string) @import_source)
+        (#match? @func_name "require")
+    )
+"#;
+
+const NAMING_QUERY: &str = r#"
+    (function_declaration name: (identifier) @func_name)
+    (method_definition name: (property_identifier) @func_name)
+    (class_declaration name: (_) @class_name)
+    (variable_declarator name: (identifier) @var_name)
+"#;
+
+const ERROR_HANDLING_QUERY: &str = r#"
+    (try_statement) @try_catch
+    (call_expression
+        function: (member_expression property: (property_identifier) @method)
+        (#eq? @method "catch")
+    ) @promise_catch
+    (class_declaration
+        (class_heritage) @heritage
+        (#match? @heritage "^extends\\s+[A-Za-z_$.]*Error\\b")
+    ) @error_class
+    (import_statement source: (string) @import_source)
+"#;
+
+const DI_QUERY: &str = r#"
+    (import_statement source: (string) @import_source)
+    (decorator (call_expression function: (identifier) @decorator_name))
+    (decorator (identifier) @decorator_name)
+    (call_expression
+        function: (member_expression
+            object: (identifier) @container
+            property: (property_identifier) @method
+        )
+        (#eq? @container "container")
+        (#match? @method "^(get|resolve)$")
+    ) @container_lookup
+"#;
+
+const TESTING_QUERY: &str = r#"
+    (import_statement source: (string) @import_source)
+    (call_expression
+        function: (identifier) @require
+        arguments: (arguments (string) @import_source)
+        (#eq? @require "require")
+    )
+    (call_expression
+        function: (member_expression
+            object: (identifier) @mock_object
+            property: (property_identifier) @mock_method
+        )
+        (#match? @mock_object "^(jest|vi|sinon)$")
+    )
+    (call_expression function: (identifier) @test_block (#match? @test_block "^(describe|it|test)$"))
+    (call_expression function: (identifier) @expect (#eq? @expect "expect"))
+"#;
+
+/// Interfaces and parameter properties only exist in the TypeScript grammars.
+const TYPESCRIPT_QUERY: &str = r#"
+    (interface_declaration name: (type_identifier) @interface_name)
+    (method_definition
+        name: (property_identifier) @constructor
+        parameters: (formal_parameters (required_parameter (accessibility_modifier)) @parameter_property)
+        (#eq? @constructor "constructor")
+    )
+"#;
+
+const LOGGING_QUERY: &str = r#"
+    (import_statement source: (string) @import_source)
+    (call_expression
+        function: (identifier) @require
+        arguments: (arguments (string) @import_source)
+        (#eq? @require "require")
+    )
+    (call_expression
+        function: (member_expression object: (_) @receiver property: (property_identifier) @level)
+        (#match? @level "^(trace|debug|info|warn|error|fatal|log)$")
+    ) @log_call
+"#;
+
+const LOGGERS: &[&str] = &["winston", "pino", "bunyan"];
+
+const DRY_QUERY: &str = r#"
+    (string) @string
+"#;
+
+/// Decorators that ask a container to construct a class or resolve a parameter.
+const INJECTION_DECORATORS: &[&str] = &["Injectable", "Inject", "injectable", "inject", "singleton", "autoInjectable"];
+
+pub(crate) struct JavaScriptQueries {
+    tech_stack: Query,
+    naming: Query,
+    error_handling: Query,
+    di: Query,
+    testing: Query,
+    /// `None` for the JavaScript grammar.
+    typescript: Option<Query>,
+    logging: Query,
+    dry: Query,
+}
+
+impl JavaScriptQueries {
+    pub(crate) fn new(lang: &Language) -> Self {
+        Self {
+            tech_stack: Query::new(lang, TECH_STACK_QUERY).unwrap(),
+            naming: Query::new(lang, NAMING_QUERY).unwrap(),
+            error_handling: Query::new(lang, ERROR_HANDLING_QUERY).unwrap(),
+            di: Query::new(lang, DI_QUERY).unwrap(),
+            testing: Query::new(lang, TESTING_QUERY).unwrap(),
+            typescript: Query::new(lang, TYPESCRIPT_QUERY).ok(),
+            logging: Query::new(lang, LOGGING_QUERY).unwrap(),
+            dry: Query::new(lang, DRY_QUERY).unwrap(),
+        }
+    }
+}
+
+// One set per grammar a JavaScript or TypeScript file can be parsed with.
+static TYPESCRIPT_QUERIES: LazyLock<JavaScriptQueries> =
+    LazyLock::new(|| JavaScriptQueries::new(&tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()));
+static TSX_QUERIES: LazyLock<JavaScriptQueries> =
+    LazyLock::new(|| JavaScriptQueries::new(&tree_sitter_typescript::LANGUAGE_TSX.into()));
+static JAVASCRIPT_QUERIES: LazyLock<JavaScriptQueries> =
+    LazyLock::new(|| JavaScriptQueries::new(&tree_sitter_javascript::LANGUAGE.into()));
+
 pub struct JavaScriptAnalyzer;
 
 impl LanguageAnalyzer for JavaScriptAnalyzer {
     fn analyze(&self, content: &str, tree: &tree_sitter::Tree, result: &mut AnalysisResult) {
-        // Gunakan TS grammar untuk JS/TS
-        let ts_lang = tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into();
-        self.analyze_naming(content, tree, &ts_lang, result);
-        self.analyze_tech_stack(content, tree, &ts_lang, result);
-        self.analyze_dry(content, tree, &ts_lang, result);
+        // The tree may come from the JavaScript, TypeScript or TSX grammar, so
+        // queries use the set compiled for whichever one produced it and only
+        // use node types the three grammars share.
+        let queries = Self::queries_for(&tree.language());
+        self.analyze_naming(content, tree, &queries.naming, result);
+        if let Some(query) = &queries.typescript {
+            self.analyze_typescript(content, tree, query, result);
+        }
+        self.analyze_error_handling(content, tree, &queries.error_handling, result);
+        self.analyze_di(content, tree, &queries.di, result);
+        self.analyze_testing(content, tree, &queries.testing, result);
+        self.analyze_logging(content, tree, &queries.logging, result);
+        self.analyze_tech_stack(content, tree, &queries.tech_stack, result);
+        self.analyze_dry(content, tree, &queries.dry, result);
     }
 }
 
 impl JavaScriptAnalyzer {
-    fn analyze_tech_stack(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (import_statement source: (string) @import_source)
-            (call_expression
-                function: (identifier) @func_name
-                arguments: (arguments (string) @import_source)
-                (#match? @func_name "require")
-            )
-        "#;
-        let query = Query::new(lang, query_str).unwrap();
+    /// The cached query set for the grammar that produced a tree.
+    fn queries_for(language: &tree_sitter::Language) -> &'static JavaScriptQueries {
+        if *language == tree_sitter_typescript::LANGUAGE_TSX.into() {
+            &TSX_QUERIES
+        } else if *language == tree_sitter_javascript::LANGUAGE.into() {
+            &JAVASCRIPT_QUERIES
+        } else {
+            &TYPESCRIPT_QUERIES
+        }
+    }
+
+    fn analyze_tech_stack(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
 
         while let Some(m) = matches.next() {
             for capture in m.captures {
@@ -48,80 +189,267 @@ impl JavaScriptAnalyzer {
                 };
 
                 if let Some(cat) = category {
-                    match cat {
-                        "framework" => if !result.tech_stack.frameworks.contains(&tech.to_string()) { result.tech_stack.frameworks.push(tech.to_string()) },
-                        "database" => if !result.tech_stack.databases.contains(&tech.to_string()) { result.tech_stack.databases.push(tech.to_string()) },
-                        "library" => if !result.tech_stack.libraries.contains(&tech.to_string()) { result.tech_stack.libraries.push(tech.to_string()) },
-                        _ => {}
-                    }
+                    result.tech_stack.add(cat, tech);
                 }
             }
         }
     }
 
-    fn analyze_naming(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (function_declaration (identifier) @func_name)
-            (method_definition (property_identifier) @func_name)
-            (class_declaration (type_identifier) @class_name)
-            (variable_declarator (identifier) @var_name)
-        "#;
-        
-        let query = Query::new(lang, query_str).unwrap();
+    fn analyze_naming(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
 
         while let Some(m) = matches.next() {
             for capture in m.captures {
                 let node = capture.node;
                 let name = &content[node.start_byte()..node.end_byte()];
-                let casing = NamingConvention::detect_casing(name);
+                let line = node.start_position().row + 1;
                 
                 let capture_name = query.capture_names()[capture.index as usize];
                 match capture_name {
-                    "func_name" => {
-                        if result.naming.function_casing == Casing::Unknown {
-                            result.naming.function_casing = casing;
-                        }
+                    "func_name" => result.naming.record_identifier(IdentifierKind::Function, name, line),
+                    "class_name" => result.naming.record_identifier(IdentifierKind::Type, name, line),
+                    "var_name" => result.naming.record_identifier(IdentifierKind::Variable, name, line),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn analyze_typescript(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let node = capture.node;
+                let name = &content[node.start_byte()..node.end_byte()];
+                let capture_name = query.capture_names()[capture.index as usize];
+                match capture_name {
+                    "interface_name" => {
+                        result.naming.record_identifier(IdentifierKind::Type, name, node.start_position().row + 1);
+                        result.naming.record_interface(name);
                     },
-                    "class_name" => {
-                        if result.naming.class_struct_naming == Casing::Unknown {
-                            result.naming.class_struct_naming = casing;
+                    // `constructor(private readonly repo: UserRepository)`
+                    "parameter_property" => push_unique(&mut result.di.injection_patterns, "Constructor Injection (parameter properties)"),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn analyze_error_handling(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let capture_name = query.capture_names()[capture.index as usize];
+                let pattern = match capture_name {
+                    "try_catch" => "try/catch",
+                    "promise_catch" => "Promise .catch()",
+                    "error_class" => "Custom Error subclasses",
+                    "import_source" => {
+                        let source = Self::import_source(content, capture.node);
+                        match source {
+                            "neverthrow" => "Result types (neverthrow)",
+                            "ts-results" | "ts-results-es" => "Result types (ts-results)",
+                            s if s == "fp-ts" || s.starts_with("fp-ts/") => "Either (fp-ts)",
+                            _ => continue,
                         }
                     },
-                    "var_name" => {
-                        if result.naming.variable_casing == Casing::Unknown {
-                            result.naming.variable_casing = casing;
-                        }
+                    _ => continue,
+                };
+                push_unique(&mut result.error_handling.failure_patterns, pattern);
+            }
+        }
+    }
+
+    fn analyze_di(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        let mut container = None;
+        let mut decorated = false;
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let text = &content[capture.node.start_byte()..capture.node.end_byte()];
+                let capture_name = query.capture_names()[capture.index as usize];
+                match capture_name {
+                    "import_source" => {
+                        let source = Self::import_source(content, capture.node);
+                        let library = match source {
+                            s if s.starts_with("@nestjs/") => "NestJS (@Injectable)",
+                            "@angular/core" => "Angular (@Injectable)",
+                            "inversify" => "Inversify (@injectable/@inject)",
+                            "tsyringe" => "tsyringe (@injectable)",
+                            _ => continue,
+                        };
+                        container.get_or_insert(library);
+                    },
+                    "decorator_name" if INJECTION_DECORATORS.contains(&text) => decorated = true,
+                    "container_lookup" => push_unique(&mut result.di.injection_patterns, "Service Locator (container.get/resolve)"),
+                    _ => {}
+                }
+            }
+        }
+
+        // The decorator names overlap between libraries, so the import decides which one it is.
+        if decorated {
+            push_unique(&mut result.di.injection_patterns, container.unwrap_or("Decorator-based Injection"));
+        }
+    }
+
+    fn analyze_testing(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        let (mut jest, mut vitest, mut mocha, mut chai, mut sinon) = (false, false, false, false, false);
+        let (mut jest_mocks, mut vi_mocks, mut module_mocks) = (false, false, false);
+        let (mut describe, mut test, mut expect) = (false, false, false);
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let text = &content[capture.node.start_byte()..capture.node.end_byte()];
+                let capture_name = query.capture_names()[capture.index as usize];
+                match capture_name {
+                    "import_source" => match Self::import_source(content, capture.node) {
+                        "@jest/globals" => jest = true,
+                        "vitest" => vitest = true,
+                        "mocha" => mocha = true,
+                        "chai" => chai = true,
+                        "sinon" => sinon = true,
+                        _ => {}
                     },
+                    "mock_object" => match text {
+                        "jest" => (jest, jest_mocks) = (true, true),
+                        "vi" => (vitest, vi_mocks) = (true, true),
+                        _ => sinon = true,
+                    },
+                    "mock_method" if text == "mock" => module_mocks = true,
+                    "test_block" if text == "test" => test = true,
+                    "test_block" => describe = true,
+                    "expect" => expect = true,
                     _ => {}
                 }
             }
         }
+
+        let testing = &mut result.testing;
+        // Jest and Vitest both expose a global `expect`; without an import or a
+        // `jest.*`/`vi.*` call, Jest is the more common of the two.
+        let assertion_style = if vitest {
+            "Vitest (expect)"
+        } else if jest {
+            "Jest (expect)"
+        } else if chai {
+            "Mocha + Chai"
+        } else if mocha {
+            "Mocha"
+        } else if expect && (describe || test) {
+            "Jest (expect)"
+        } else {
+            ""
+        };
+        if !assertion_style.is_empty() {
+            testing.assertion_style = assertion_style.to_string();
+            if describe {
+                testing.naming_pattern = "describe/it blocks".to_string();
+            } else if test {
+                testing.naming_pattern = "test() blocks".to_string();
+            }
+        }
+
+        let mocking_strategy = match (jest_mocks, vi_mocks, sinon) {
+            (true, _, _) if module_mocks => "jest.mock",
+            (true, _, _) => "jest.fn/jest.spyOn",
+            (_, true, _) if module_mocks => "vi.mock",
+            (_, true, _) => "vi.fn/vi.spyOn",
+            (_, _, true) => "sinon",
+            _ => "",
+        };
+        if !mocking_strategy.is_empty() {
+            testing.mocking_strategy = mocking_strategy.to_string();
+        }
     }
 
-    fn analyze_dry(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (string) @string
-        "#;
-        let query = Query::new(lang, query_str).unwrap();
+    fn analyze_logging(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let text = |node: tree_sitter::Node| &content[node.start_byte()..node.end_byte()];
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        let mut file_loggers = Vec::new();
+        let mut calls = Vec::new();
+        while let Some(m) = matches.next() {
+            let capture = |name: &str| m.captures.iter()
+                .find(|c| query.capture_names()[c.index as usize] == name)
+                .map(|c| c.node);
+            if let Some(source) = capture("import_source") {
+                let source = Self::import_source(content, source);
+                if let Some(logger) = LOGGERS.iter().find(|logger| **logger == source) {
+                    if !file_loggers.contains(logger) {
+                        file_loggers.push(*logger);
+                    }
+                }
+            } else if let (Some(call), Some(receiver), Some(level)) = (capture("log_call"), capture("receiver"), capture("level")) {
+                calls.push((call, receiver, text(level)));
+            }
+        }
+
+        for (call, receiver, level) in calls {
+            let receiver = text(receiver);
+            if receiver == "console" {
+                // `console.error` and `console.warn` are how many scripts report
+                // failures; only debugging output counts as stray.
+                if level == "log" || level == "debug" {
+                    result.error_handling.logging.record_stray_print(&format!("console.{}", level), call.start_position().row + 1);
+                }
+                continue;
+            }
+            let logger = match LOGGERS.iter().find(|logger| **logger == receiver) {
+                Some(logger) => *logger,
+                None if receiver.to_lowercase().contains("log") => match file_loggers.as_slice() {
+                    [logger] => *logger,
+                    _ => continue,
+                },
+                None => continue,
+            };
+
+            // `logger.info({ userId }, "msg")` for pino, `logger.info("msg", { userId })` for winston.
+            let structured = call.child_by_field_name("arguments").is_some_and(|arguments| {
+                let mut walker = arguments.walk();
+                let fields = arguments.named_children(&mut walker).any(|argument| argument.kind() == "object");
+                fields
+            });
+            result.error_handling.logging.record_call(logger, structured);
+        }
+    }
+
+    fn import_source<'a>(content: &'a str, node: tree_sitter::Node) -> &'a str {
+        content[node.start_byte()..node.end_byte()].trim_matches(|c| c == '\'' || c == '"')
+    }
+
+    fn analyze_dry(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
 
         let mut strings = std::collections::HashMap::new();
         while let Some(m) = matches.next() {
             for capture in m.captures {
                 let text = &content[capture.node.start_byte()..capture.node.end_byte()];
                 if text.len() > 10 {
-                    *strings.entry(text).or_insert(0) += 1;
+                    strings.entry(text).or_insert((0, capture.node.start_position().row + 1)).0 += 1;
                 }
             }
         }
 
-        for (text, count) in strings {
+        for (text, (count, line)) in strings {
             if count > 1 {
-                result.dry.duplicated_blocks.push(format!("String literal repeated {} times: {}", count, text));
-                result.dry.duplication_score += (count - 1) as f64 * 0.1;
+                result.dry.duplicated_blocks.push(DuplicatedBlock {
+                    file: String::new(),
+                    line,
+                    description: format!("String literal repeated {} times: {}", count, text),
+                });
+                result.dry.repeated_literals += count - 1;
             }
         }
     }
@@ -131,6 +459,9 @@ impl JavaScriptAnalyzer {
 mod tests {
     use super::*;
     use crate::rules::AnalysisResult;
+    use crate::rules::naming::Casing;
+    use crate::parser::{CodeParser, SupportedLanguage};
+    use std::path::Path;
     use tree_sitter::Parser;
 
     #[test]
@@ -156,4 +487,167 @@ class UserProfile {
         assert_eq!(result.naming.class_struct_naming, Casing::PascalCase);
         assert_eq!(result.naming.variable_casing, Casing::CamelCase);
     }
+
+    fn analyze_file(file_name: &str, content: &str) -> (tree_sitter::Tree, AnalysisResult) {
+        let parser = CodeParser::new(Path::new(file_name)).unwrap();
+        let tree = parser.parse(content).unwrap();
+        let mut result = AnalysisResult::default();
+        JavaScriptAnalyzer.analyze(content, &tree, &mut result);
+        (tree, result)
+    }
+
+    #[test]
+    fn test_plain_javascript_grammar() {
+        let content = r#"
+const express = require("express");
+class UserService {
+    findUser(userId) {
+        const cachedUser = null;
+        return cachedUser;
+    }
+}
+"#;
+        let (tree, result) = analyze_file("service.cjs", content);
+
+        assert!(!tree.root_node().has_error());
+        assert_eq!(result.naming.class_struct_naming, Casing::PascalCase);
+        assert_eq!(result.naming.function_casing, Casing::CamelCase);
+        assert!(result.tech_stack.frameworks.iter().any(|entry| *entry == "Express"));
+    }
+
+    #[test]
+    fn test_query_sets_are_compiled_once_per_grammar() {
+        let typescript: tree_sitter::Language = tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into();
+        let tsx: tree_sitter::Language = tree_sitter_typescript::LANGUAGE_TSX.into();
+        let javascript: tree_sitter::Language = tree_sitter_javascript::LANGUAGE.into();
+
+        let first = JavaScriptAnalyzer::queries_for(&typescript);
+        let second = JavaScriptAnalyzer::queries_for(&typescript);
+        assert!(std::ptr::eq(first, second));
+        assert!(std::ptr::eq(first, &*TYPESCRIPT_QUERIES));
+        assert!(std::ptr::eq(JavaScriptAnalyzer::queries_for(&tsx), &*TSX_QUERIES));
+        assert!(std::ptr::eq(JavaScriptAnalyzer::queries_for(&javascript), &*JAVASCRIPT_QUERIES));
+        assert!(!std::ptr::eq(&*TSX_QUERIES, &*TYPESCRIPT_QUERIES));
+    }
+
+    #[test]
+    fn test_jsx_and_tsx_parse_without_errors() {
+        let jsx = r#"
+import React from "react";
+function UserCard({ userName }) {
+    const displayName = userName.trim();
+    return <div className="card">{displayName}</div>;
+}
+"#;
+        let tsx = r#"
+import React from "react";
+interface Props { userName: string }
+export function UserCard({ userName }: Props) {
+    const displayName: string = userName.trim();
+    return <div className="card">{displayName}</div>;
+}
+"#;
+        for (file_name, content) in [("UserCard.jsx", jsx), ("UserCard.tsx", tsx)] {
+            let (tree, result) = analyze_file(file_name, content);
+            assert!(!tree.root_node().has_error(), "{} should parse cleanly", file_name);
+            assert_eq!(result.naming.function_casing, Casing::PascalCase);
+            assert!(result.tech_stack.libraries.iter().any(|entry| *entry == "React"));
+        }
+    }
+
+    #[test]
+    fn test_typescript_di_and_error_handling() {
+        let content = r#"
+import { Injectable } from "@nestjs/common";
+import { ok, err, Result } from "neverthrow";
+
+export interface IUserRepository { find(id: string): Promise<User> }
+
+export class UserNotFoundError extends Error {}
+
+@Injectable()
+export class UserService {
+    constructor(private readonly users: IUserRepository) {}
+
+    async load(id: string) {
+        try {
+            return await this.users.find(id);
+        } catch (e) {
+            return this.fallback(id).catch(() => null);
+        }
+    }
+}
+"#;
+        let (_, result) = analyze_file("user.service.ts", content);
+
+        assert_eq!(result.naming.interface_prefix.as_deref(), Some("I"));
+        assert_eq!(result.di.injection_patterns, vec!["Constructor Injection (parameter properties)", "NestJS (@Injectable)"]);
+        assert_eq!(result.error_handling.failure_patterns, vec![
+            "Result types (neverthrow)",
+            "Custom Error subclasses",
+            "try/catch",
+            "Promise .catch()",
+        ]);
+    }
+
+    #[test]
+    fn test_test_framework_detection() {
+        let jest = r#"
+jest.mock("./db");
+describe("UserService", () => {
+    it("loads users", () => {
+        expect(load()).toBeDefined();
+    });
+});
+"#;
+        let (_, result) = analyze_file("user.spec.ts", jest);
+        assert_eq!(result.testing.assertion_style, "Jest (expect)");
+        assert_eq!(result.testing.mocking_strategy, "jest.mock");
+        assert_eq!(result.testing.naming_pattern, "describe/it blocks");
+
+        let mocha = r#"
+const { expect } = require("chai");
+const sinon = require("sinon");
+describe("cart", () => {
+    it("totals", () => {
+        const stub = sinon.stub(prices, "lookup");
+        expect(total()).to.equal(3);
+    });
+});
+"#;
+        let (_, result) = analyze_file("cart.test.js", mocha);
+        assert_eq!(result.testing.assertion_style, "Mocha + Chai");
+        assert_eq!(result.testing.mocking_strategy, "sinon");
+    }
+
+    #[test]
+    fn test_logging_analysis() {
+        let content = r#"
+import pino from "pino";
+const logger = pino();
+
+export function handle(req) {
+    logger.info({ path: req.path }, "request");
+    logger.error(`failed ${req.path}`);
+    console.log("handled", req.path);
+    console.error("kept: scripts report failures this way");
+}
+"#;
+        let (_, result) = analyze_file("handler.ts", content);
+        let logging = &result.error_handling.logging;
+        assert_eq!(logging.logger_calls.get("pino"), Some(&2));
+        assert_eq!((logging.structured_calls, logging.printf_calls), (1, 1));
+        assert_eq!(logging.stray_prints.len(), 1);
+        assert_eq!(logging.stray_prints[0].call, "console.log");
+    }
+
+    #[test]
+    fn test_extensions_map_to_separate_languages() {
+        let language = |name: &str| CodeParser::new(Path::new(name)).unwrap().language;
+        assert_eq!(language("index.js"), SupportedLanguage::JavaScript);
+        assert_eq!(language("index.mjs"), SupportedLanguage::JavaScript);
+        assert_eq!(language("App.jsx"), SupportedLanguage::JavaScript);
+        assert_eq!(language("index.ts"), SupportedLanguage::TypeScript);
+        assert_eq!(language("App.tsx"), SupportedLanguage::TypeScript);
+    }
 }
@@ -2,19 +2,48 @@ pub mod go;
 pub mod python;
 pub mod javascript;
 pub mod rust;
+pub mod java;
 
 use crate::rules::AnalysisResult;
 use crate::parser::SupportedLanguage;
+use crate::plugins::{PluginAnalyzer, PluginRegistry};
 
 pub trait LanguageAnalyzer {
     fn analyze(&self, content: &str, tree: &tree_sitter::Tree, result: &mut AnalysisResult);
 }
 
-pub fn get_analyzer(lang: SupportedLanguage) -> Box<dyn LanguageAnalyzer + Send + Sync> {
+/// The analyzer for `lang`; plugin languages are looked up in `plugins`.
+pub fn get_analyzer(lang: SupportedLanguage, plugins: &PluginRegistry) -> Box<dyn LanguageAnalyzer + Send + Sync> {
     match lang {
         SupportedLanguage::Go => Box::new(go::GoAnalyzer),
         SupportedLanguage::Python => Box::new(python::PythonAnalyzer),
         SupportedLanguage::TypeScript | SupportedLanguage::JavaScript => Box::new(javascript::JavaScriptAnalyzer),
         SupportedLanguage::Rust => Box::new(rust::RustAnalyzer),
+        SupportedLanguage::Java => Box::new(java::JavaAnalyzer),
+        SupportedLanguage::Plugin(name) => Box::new(PluginAnalyzer::new(name, plugins)),
+    }
+}
+
+/// Adds a detected pattern once, keeping the order patterns were first seen in.
+pub(crate) fn push_unique(patterns: &mut Vec<String>, pattern: &str) {
+    if !patterns.iter().any(|existing| existing == pattern) {
+        patterns.push(pattern.to_string());
+    }
+}
+
+/// Compiles a language's queries from scratch instead of using the set the
+/// analyzer caches for the process lifetime. Only useful for measuring what
+/// the cache saves, so it is only built with the `bench` feature.
+#[cfg(feature = "bench")]
+pub fn compile_queries(lang: SupportedLanguage) {
+    match lang {
+        SupportedLanguage::Go => drop(go::GoQueries::new(&tree_sitter_go::LANGUAGE.into())),
+        SupportedLanguage::Python => drop(python::PythonQueries::new(&tree_sitter_python::LANGUAGE.into())),
+        SupportedLanguage::TypeScript => drop(javascript::JavaScriptQueries::new(&tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into())),
+        SupportedLanguage::JavaScript => drop(javascript::JavaScriptQueries::new(&tree_sitter_javascript::LANGUAGE.into())),
+        SupportedLanguage::Rust => drop(rust::RustQueries::new(&tree_sitter_rust::LANGUAGE.into())),
+        SupportedLanguage::Java => drop(java::JavaQueries::new(&tree_sitter_java::LANGUAGE.into())),
+        // Plugin queries are compiled once, when the plugin is loaded.
+        SupportedLanguage::Plugin(_) => {}
     }
 }
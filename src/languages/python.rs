@@ -1,29 +1,156 @@
-use crate::languages::LanguageAnalyzer;
+use crate::languages::{LanguageAnalyzer, push_unique};
 use crate::rules::AnalysisResult;
-use crate::rules::naming::{NamingConvention, Casing};
-use tree_sitter::Query;
+use crate::rules::naming::{Casing, IdentifierKind};
+use crate::rules::dry::DuplicatedBlock;
+use std::sync::LazyLock;
+use tree_sitter::{Language, Query};
 use streaming_iterator::StreamingIterator;
 
+const TECH_STACK_QUERY: &str = r#"
+    (import_from_statement (dotted_name) @module_name)
+    (import_statement (dotted_name) @module_name)
+"#;
+
+const NAMING_QUERY: &str = r#"
+    (function_definition name: (identifier) @func_name)
+    (class_definition name: (identifier) @class_name)
+    (assignment left: (identifier) @var_name)
+"#;
+
+const ERROR_HANDLING_QUERY: &str = r#"
+    (except_clause) @except
+    (raise_statement cause: (_)) @raise_from
+    (class_definition
+        superclasses: (argument_list [(identifier) (attribute)] @base)
+        (#match? @base "(Exception|Error)$")
+    ) @exception_class
+"#;
+
+const DI_QUERY: &str = r#"
+    (function_definition
+        name: (identifier) @init_name
+        (#eq? @init_name "__init__")
+    ) @constructor
+    (call function: (identifier) @depends (#eq? @depends "Depends"))
+    (call function: (attribute attribute: (identifier) @depends) (#eq? @depends "Depends"))
+    (import_statement name: (dotted_name) @module (#match? @module "^dependency_injector"))
+    (import_from_statement module_name: (dotted_name) @module (#match? @module "^dependency_injector"))
+    (global_statement (identifier) @global_name)
+"#;
+
+const TESTING_QUERY: &str = r#"
+    (import_statement name: (dotted_name) @module)
+    (import_from_statement module_name: (dotted_name) @module)
+    (import_from_statement
+        module_name: (dotted_name) @from_module
+        name: (dotted_name) @imported
+        (#eq? @from_module "unittest")
+        (#eq? @imported "mock")
+    ) @unittest_mock
+    (class_definition
+        superclasses: (argument_list [(identifier) (attribute)] @test_case)
+        (#match? @test_case "TestCase$")
+    )
+    (module (function_definition name: (identifier) @test_function (#match? @test_function "^test_")))
+    (module (decorated_definition
+        definition: (function_definition name: (identifier) @test_function (#match? @test_function "^test_"))))
+    (decorator) @decorator
+    (parameters (identifier) @mocker (#eq? @mocker "mocker"))
+"#;
+
+const DESIGN_PATTERNS_QUERY: &str = r#"
+    (decorator (call function: (identifier) @wraps (#eq? @wraps "wraps")))
+    (decorator (call function: (attribute attribute: (identifier) @wraps) (#eq? @wraps "wraps")))
+    (class_definition
+        superclasses: (argument_list (identifier) @abc_base)
+        (#eq? @abc_base "ABC")
+    )
+    (keyword_argument
+        name: (identifier) @keyword
+        value: (_) @metaclass
+        (#eq? @keyword "metaclass")
+        (#match? @metaclass "ABCMeta$")
+    )
+    (decorator (identifier) @abstract (#eq? @abstract "abstractmethod"))
+    (decorator (attribute attribute: (identifier) @abstract) (#eq? @abstract "abstractmethod"))
+    (class_definition
+        superclasses: (argument_list (identifier) @protocol_base)
+        (#eq? @protocol_base "Protocol")
+    )
+    (module (expression_statement (assignment left: (identifier) @module_global right: (none))))
+    (global_statement (identifier) @global_name)
+"#;
+
+const LOGGING_QUERY: &str = r#"
+    (import_statement name: (dotted_name) @import)
+    (import_from_statement module_name: (dotted_name) @import)
+    (call
+        function: (attribute object: (_) @receiver attribute: (identifier) @level)
+        (#match? @level "^(debug|info|warning|warn|error|exception|critical|trace|success)$")
+    ) @log_call
+    (call function: (identifier) @print (#eq? @print "print")) @stray_print
+"#;
+
+const LOGGERS: &[&str] = &["logging", "structlog", "loguru"];
+
+/// Keyword arguments of the `logging` API that don't carry data.
+const LOGGING_OPTIONS: &[&str] = &["exc_info", "stack_info", "stacklevel"];
+
+const DRY_QUERY: &str = r#"
+    (string) @string
+"#;
+
+/// Catching these is as broad as a bare `except`, minus `KeyboardInterrupt`.
+const BROAD_EXCEPTIONS: &[&str] = &["Exception", "BaseException"];
+
+pub(crate) struct PythonQueries {
+    tech_stack: Query,
+    naming: Query,
+    error_handling: Query,
+    di: Query,
+    testing: Query,
+    design_patterns: Query,
+    logging: Query,
+    dry: Query,
+}
+
+impl PythonQueries {
+    pub(crate) fn new(lang: &Language) -> Self {
+        Self {
+            tech_stack: Query::new(lang, TECH_STACK_QUERY).unwrap(),
+            naming: Query::new(lang, NAMING_QUERY).unwrap(),
+            error_handling: Query::new(lang, ERROR_HANDLING_QUERY).unwrap(),
+            di: Query::new(lang, DI_QUERY).unwrap(),
+            testing: Query::new(lang, TESTING_QUERY).unwrap(),
+            design_patterns: Query::new(lang, DESIGN_PATTERNS_QUERY).unwrap(),
+            logging: Query::new(lang, LOGGING_QUERY).unwrap(),
+            dry: Query::new(lang, DRY_QUERY).unwrap(),
+        }
+    }
+}
+
+static QUERIES: LazyLock<PythonQueries> = LazyLock::new(|| PythonQueries::new(&tree_sitter_python::LANGUAGE.into()));
+
 pub struct PythonAnalyzer;
 
 impl LanguageAnalyzer for PythonAnalyzer {
     fn analyze(&self, content: &str, tree: &tree_sitter::Tree, result: &mut AnalysisResult) {
-        let ts_lang = tree_sitter_python::LANGUAGE.into();
-        self.analyze_naming(content, tree, &ts_lang, result);
-        self.analyze_tech_stack(content, tree, &ts_lang, result);
-        self.analyze_dry(content, tree, &ts_lang, result);
+        let queries = &*QUERIES;
+        self.analyze_naming(content, tree, &queries.naming, result);
+        self.analyze_error_handling(content, tree, &queries.error_handling, result);
+        self.analyze_di(content, tree, &queries.di, result);
+        self.analyze_design_patterns(content, tree, &queries.design_patterns, result);
+        self.analyze_testing(content, tree, &queries.testing, result);
+        self.analyze_logging(content, tree, &queries.logging, result);
+        self.analyze_tech_stack(content, tree, &queries.tech_stack, result);
+        self.analyze_dry(content, tree, &queries.dry, result);
     }
 }
 
 impl PythonAnalyzer {
-    fn analyze_tech_stack(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (import_from_statement (dotted_name) @module_name)
-            (import_statement (dotted_name) @module_name)
-        "#;
-        let query = Query::new(lang, query_str).unwrap();
+    fn analyze_tech_stack(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
 
         while let Some(m) = matches.next() {
             for capture in m.captures {
@@ -42,50 +169,92 @@ impl PythonAnalyzer {
                 };
 
                 if let Some(cat) = category {
-                    match cat {
-                        "framework" => if !result.tech_stack.frameworks.contains(&tech.to_string()) { result.tech_stack.frameworks.push(tech.to_string()) },
-                        "database" => if !result.tech_stack.databases.contains(&tech.to_string()) { result.tech_stack.databases.push(tech.to_string()) },
-                        "library" => if !result.tech_stack.libraries.contains(&tech.to_string()) { result.tech_stack.libraries.push(tech.to_string()) },
-                        _ => {}
-                    }
+                    result.tech_stack.add(cat, tech);
                 }
             }
         }
     }
 
-    fn analyze_naming(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (function_definition name: (identifier) @func_name)
-            (class_definition name: (identifier) @class_name)
-            (assignment left: (identifier) @var_name)
-        "#;
-        
-        let query = Query::new(lang, query_str).unwrap();
+    fn analyze_naming(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
 
         while let Some(m) = matches.next() {
             for capture in m.captures {
                 let node = capture.node;
                 let name = &content[node.start_byte()..node.end_byte()];
-                let casing = NamingConvention::detect_casing(name);
+                let line = node.start_position().row + 1;
                 
                 let capture_name = query.capture_names()[capture.index as usize];
                 match capture_name {
-                    "func_name" => {
-                        if result.naming.function_casing == Casing::Unknown {
-                            result.naming.function_casing = casing;
+                    "func_name" => result.naming.record_identifier(IdentifierKind::Function, name, line),
+                    "class_name" => result.naming.record_identifier(IdentifierKind::Type, name, line),
+                    "var_name" if Self::is_constant(node, name) => result.naming.record_identifier(IdentifierKind::Constant, name, line),
+                    "var_name" => result.naming.record_identifier(IdentifierKind::Variable, name, line),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// An UPPER_SNAKE_CASE name assigned at module or class level, such as
+    /// `MAX_RETRIES = 3`, which PEP 8 reserves for constants.
+    fn is_constant(node: tree_sitter::Node, name: &str) -> bool {
+        let scope = node.parent()
+            .filter(|assignment| assignment.kind() == "assignment")
+            .and_then(|assignment| assignment.parent())
+            .filter(|statement| statement.kind() == "expression_statement")
+            .and_then(|statement| statement.parent());
+        let top_level = scope.is_some_and(|scope| scope.kind() == "module"
+            || (scope.kind() == "block" && scope.parent().is_some_and(|class| class.kind() == "class_definition")));
+        top_level && Casing::UpperSnakeCase.accepts(name)
+    }
+
+    fn analyze_error_handling(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let capture_name = query.capture_names()[capture.index as usize];
+                let pattern = match capture_name {
+                    "except" => match capture.node.child_by_field_name("value") {
+                        None => "Bare except",
+                        Some(value) => {
+                            // `except Exception as e` wraps the type in an `as_pattern`.
+                            let caught = if value.kind() == "as_pattern" { value.named_child(0).unwrap_or(value) } else { value };
+                            if BROAD_EXCEPTIONS.contains(&&content[caught.start_byte()..caught.end_byte()]) {
+                                "except Exception"
+                            } else {
+                                "Specific exceptions (try/except)"
+                            }
                         }
                     },
-                    "class_name" => {
-                        if result.naming.class_struct_naming == Casing::Unknown {
-                            result.naming.class_struct_naming = casing;
-                        }
+                    "raise_from" => "Exception chaining (raise ... from)",
+                    "exception_class" => "Custom exception hierarchy",
+                    _ => continue,
+                };
+                push_unique(&mut result.error_handling.failure_patterns, pattern);
+            }
+        }
+    }
+
+    fn analyze_di(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let capture_name = query.capture_names()[capture.index as usize];
+                match capture_name {
+                    "constructor" if Self::stores_parameters(capture.node, content) => {
+                        push_unique(&mut result.di.injection_patterns, "Constructor Injection (__init__)");
                     },
-                    "var_name" => {
-                        if result.naming.variable_casing == Casing::Unknown {
-                            result.naming.variable_casing = casing;
-                        }
+                    "depends" => push_unique(&mut result.di.injection_patterns, "FastAPI Depends"),
+                    "module" => push_unique(&mut result.di.injection_patterns, "dependency-injector (Containers & Providers)"),
+                    "global_name" => {
+                        let name = &content[capture.node.start_byte()..capture.node.end_byte()];
+                        push_unique(&mut result.di.global_state_usage, &format!("global {}", name));
                     },
                     _ => {}
                 }
@@ -93,28 +262,201 @@ impl PythonAnalyzer {
         }
     }
 
-    fn analyze_dry(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (string) @string
-        "#;
-        let query = Query::new(lang, query_str).unwrap();
+    /// Whether an `__init__` assigns one of its parameters to `self`, which is
+    /// how Python classes receive their collaborators.
+    fn stores_parameters(function: tree_sitter::Node, content: &str) -> bool {
+        let text = |node: tree_sitter::Node| &content[node.start_byte()..node.end_byte()];
+        let (Some(parameters), Some(body)) = (function.child_by_field_name("parameters"), function.child_by_field_name("body")) else {
+            return false;
+        };
+
+        let mut names = Vec::new();
+        let mut walker = parameters.walk();
+        for parameter in parameters.named_children(&mut walker) {
+            let name = match parameter.kind() {
+                "identifier" => Some(parameter),
+                "typed_parameter" => parameter.named_child(0),
+                "default_parameter" | "typed_default_parameter" => parameter.child_by_field_name("name"),
+                _ => None,
+            };
+            if let Some(name) = name.map(text).filter(|name| *name != "self") {
+                names.push(name);
+            }
+        }
+
+        let mut stack = vec![body];
+        while let Some(node) = stack.pop() {
+            if node.kind() == "assignment" {
+                let target = node.child_by_field_name("left")
+                    .filter(|left| left.kind() == "attribute")
+                    .and_then(|left| left.child_by_field_name("object"));
+                let value = node.child_by_field_name("right");
+                if let (Some(target), Some(value)) = (target, value) {
+                    if text(target) == "self" && names.contains(&text(value)) {
+                        return true;
+                    }
+                }
+            }
+            let mut walker = node.walk();
+            stack.extend(node.named_children(&mut walker));
+        }
+        false
+    }
+
+    fn analyze_testing(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        let (mut pytest, mut unittest, mut fixtures) = (false, false, false);
+        let (mut pytest_mock, mut unittest_mock) = (false, false);
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let text = &content[capture.node.start_byte()..capture.node.end_byte()];
+                let capture_name = query.capture_names()[capture.index as usize];
+                match capture_name {
+                    "module" if text == "pytest" => pytest = true,
+                    "module" if text == "unittest.mock" || text == "mock" => unittest_mock = true,
+                    "unittest_mock" => unittest_mock = true,
+                    "test_case" => unittest = true,
+                    "test_function" => pytest = true,
+                    "decorator" if text.starts_with("@pytest.fixture") || text.starts_with("@fixture") => fixtures = true,
+                    "mocker" => pytest_mock = true,
+                    _ => {}
+                }
+            }
+        }
+
+        let testing = &mut result.testing;
+        // A `TestCase` subclass decides the style even when pytest runs it.
+        if unittest {
+            testing.assertion_style = "unittest (self.assert*)".to_string();
+            testing.naming_pattern = "TestCase classes with test_* methods".to_string();
+        } else if pytest || fixtures || pytest_mock {
+            testing.assertion_style = if fixtures { "pytest (plain assert) with fixtures" } else { "pytest (plain assert)" }.to_string();
+            testing.naming_pattern = "test_* functions".to_string();
+        }
+        if pytest_mock {
+            testing.mocking_strategy = "pytest-mock (mocker fixture)".to_string();
+        } else if unittest_mock {
+            testing.mocking_strategy = "unittest.mock".to_string();
+        }
+    }
+
+    fn analyze_design_patterns(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        let mut module_globals = Vec::new();
+        let mut global_names = Vec::new();
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let text = &content[capture.node.start_byte()..capture.node.end_byte()];
+                let capture_name = query.capture_names()[capture.index as usize];
+                let pattern = match capture_name {
+                    "wraps" => "Decorator (functools.wraps)",
+                    "abc_base" | "metaclass" | "abstract" => "Abstract Base Classes (abc)",
+                    "protocol_base" => "Protocol (structural typing)",
+                    "module_global" => {
+                        module_globals.push(text);
+                        continue;
+                    },
+                    "global_name" => {
+                        global_names.push(text);
+                        continue;
+                    },
+                    _ => continue,
+                };
+                push_unique(&mut result.design_patterns.patterns, pattern);
+            }
+        }
+
+        // `_client = None` at module level, filled in lazily behind `global _client`.
+        if global_names.iter().any(|name| module_globals.contains(name)) {
+            push_unique(&mut result.design_patterns.patterns, "Singleton (module global)");
+        }
+    }
+
+    fn analyze_logging(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let text = |node: tree_sitter::Node| &content[node.start_byte()..node.end_byte()];
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        let mut file_loggers = Vec::new();
+        let mut calls = Vec::new();
+        while let Some(m) = matches.next() {
+            let capture = |name: &str| m.captures.iter()
+                .find(|c| query.capture_names()[c.index as usize] == name)
+                .map(|c| c.node);
+            if let Some(import) = capture("import") {
+                let module = text(import).split('.').next().unwrap_or_default();
+                if let Some(logger) = LOGGERS.iter().find(|logger| **logger == module) {
+                    if !file_loggers.contains(logger) {
+                        file_loggers.push(*logger);
+                    }
+                }
+            } else if let Some(call) = capture("stray_print") {
+                result.error_handling.logging.record_stray_print("print", call.start_position().row + 1);
+            } else if let (Some(call), Some(receiver)) = (capture("log_call"), capture("receiver")) {
+                calls.push((call, receiver));
+            }
+        }
+
+        // `log = structlog.get_logger()` next to `import logging` is structlog.
+        if file_loggers.len() > 1 {
+            file_loggers.retain(|logger| *logger != "logging");
+        }
+        for (call, receiver) in calls {
+            // `logging.info(...)` and `structlog.get_logger().info(...)` name the library.
+            let mut root = receiver;
+            while let Some(inner) = root.child_by_field_name("object").or_else(|| root.child_by_field_name("function")) {
+                root = inner;
+            }
+            let logger = match LOGGERS.iter().find(|logger| **logger == text(root)) {
+                Some(logger) => *logger,
+                // `logger.info(...)` on a logger created elsewhere in the file.
+                None if text(receiver).to_lowercase().ends_with("log") || text(receiver).to_lowercase().ends_with("logger") => {
+                    match file_loggers.as_slice() {
+                        [logger] => *logger,
+                        _ => continue,
+                    }
+                },
+                None => continue,
+            };
+
+            let structured = call.child_by_field_name("arguments").is_some_and(|arguments| {
+                let mut walker = arguments.walk();
+                let fields = arguments.named_children(&mut walker)
+                    .filter(|argument| argument.kind() == "keyword_argument")
+                    .filter_map(|argument| argument.child_by_field_name("name"))
+                    .any(|name| !LOGGING_OPTIONS.contains(&text(name)));
+                fields
+            });
+            result.error_handling.logging.record_call(logger, structured);
+        }
+    }
+
+    fn analyze_dry(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
 
         let mut strings = std::collections::HashMap::new();
         while let Some(m) = matches.next() {
             for capture in m.captures {
                 let text = &content[capture.node.start_byte()..capture.node.end_byte()];
                 if text.len() > 10 {
-                    *strings.entry(text).or_insert(0) += 1;
+                    strings.entry(text).or_insert((0, capture.node.start_position().row + 1)).0 += 1;
                 }
             }
         }
 
-        for (text, count) in strings {
+        for (text, (count, line)) in strings {
             if count > 1 {
-                result.dry.duplicated_blocks.push(format!("String literal repeated {} times: {}", count, text));
-                result.dry.duplication_score += (count - 1) as f64 * 0.1;
+                result.dry.duplicated_blocks.push(DuplicatedBlock {
+                    file: String::new(),
+                    line,
+                    description: format!("String literal repeated {} times: {}", count, text),
+                });
+                result.dry.repeated_literals += count - 1;
             }
         }
     }
@@ -125,6 +467,115 @@ mod tests {
     use super::*;
     use crate::rules::AnalysisResult;
     use tree_sitter::Parser;
+    use crate::parser::SupportedLanguage;
+
+    fn analyze(content: &str) -> AnalysisResult {
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_python::LANGUAGE.into()).unwrap();
+        let tree = parser.parse(content, None).unwrap();
+        let mut result = AnalysisResult::default();
+        PythonAnalyzer.analyze(content, &tree, &mut result);
+        result
+    }
+
+    #[test]
+    fn test_python_error_handling_di_and_patterns() {
+        let result = analyze(r#"
+from abc import ABC, abstractmethod
+from fastapi import Depends
+
+_client = None
+
+class RepositoryError(Exception):
+    pass
+
+class Repository(ABC):
+    @abstractmethod
+    def get(self, key): ...
+
+class UserService:
+    def __init__(self, repo: Repository, retries=3):
+        self.repo = repo
+        self.retries = retries
+
+    def load(self, key):
+        try:
+            return self.repo.get(key)
+        except KeyError as err:
+            raise RepositoryError(key) from err
+        except Exception:
+            return None
+
+def get_client():
+    global _client
+    if _client is None:
+        _client = connect()
+    return _client
+
+def read_user(service: UserService = Depends(get_service)):
+    pass
+"#);
+
+        assert_eq!(result.error_handling.failure_patterns, vec![
+            "Custom exception hierarchy",
+            "Specific exceptions (try/except)",
+            "Exception chaining (raise ... from)",
+            "except Exception",
+        ]);
+        assert_eq!(result.di.injection_patterns, vec!["Constructor Injection (__init__)", "FastAPI Depends"]);
+        assert_eq!(result.di.global_state_usage, vec!["global _client"]);
+        assert_eq!(result.design_patterns.patterns, vec!["Abstract Base Classes (abc)", "Singleton (module global)"]);
+    }
+
+    #[test]
+    fn test_python_testing_frameworks() {
+        let pytest = analyze(r#"
+import pytest
+
+@pytest.fixture
+def service():
+    return Service()
+
+def test_greets(service, mocker):
+    mocker.patch("app.clock")
+    assert service.greet() == "hi"
+"#);
+        assert_eq!(pytest.testing.assertion_style, "pytest (plain assert) with fixtures");
+        assert_eq!(pytest.testing.mocking_strategy, "pytest-mock (mocker fixture)");
+
+        let unittest = analyze(r#"
+import unittest
+from unittest import mock
+
+class ServiceTest(unittest.TestCase):
+    def test_greets(self):
+        self.assertEqual(Service().greet(), "hi")
+"#);
+        assert_eq!(unittest.testing.assertion_style, "unittest (self.assert*)");
+        assert_eq!(unittest.testing.naming_pattern, "TestCase classes with test_* methods");
+        assert_eq!(unittest.testing.mocking_strategy, "unittest.mock");
+    }
+
+    #[test]
+    fn test_python_logging_analysis() {
+        let result = analyze(r#"
+import logging
+import structlog
+
+log = structlog.get_logger()
+logger = logging.getLogger(__name__)
+
+def charge(order):
+    log.info("charged", order_id=order.id)
+    logging.warning("retrying %s", order.id, exc_info=True)
+    print("charged", order.id)
+"#);
+        let logging = &result.error_handling.logging;
+        assert_eq!(logging.logger_calls.get("structlog"), Some(&1));
+        assert_eq!(logging.logger_calls.get("logging"), Some(&1));
+        assert_eq!((logging.structured_calls, logging.printf_calls), (1, 1));
+        assert_eq!(logging.stray_prints[0].line, 11);
+    }
 
     #[test]
     fn test_python_naming_analysis() {
@@ -147,4 +598,29 @@ class UserProfile:
         assert_eq!(result.naming.class_struct_naming, Casing::PascalCase);
         assert_eq!(result.naming.variable_casing, Casing::SnakeCase);
     }
+
+    #[test]
+    fn test_python_constants_stay_out_of_variable_vote() {
+        let result = analyze(r#"
+MAX_RETRIES = 3
+DEFAULT_TIMEOUT = 30
+DEBUG = False
+
+class Client:
+    BASE_URL = "https://example.com"
+
+def fetch(user_id):
+    user_name = lookup(user_id)
+    RETRY_LIMIT = 2
+    return user_name
+"#);
+        assert_eq!(result.naming.variable_casing, Casing::SnakeCase);
+        let constants: Vec<&str> = result.naming.identifiers.iter()
+            .filter(|identifier| identifier.kind == IdentifierKind::Constant)
+            .map(|identifier| identifier.name.as_str())
+            .collect();
+        assert_eq!(constants, vec!["MAX_RETRIES", "DEFAULT_TIMEOUT", "DEBUG", "BASE_URL"]);
+        let violations: Vec<_> = result.naming.violations(SupportedLanguage::Python).map(|v| v.name).collect();
+        assert_eq!(violations, vec!["RETRY_LIMIT"]);
+    }
 }
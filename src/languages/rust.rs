@@ -1,29 +1,143 @@
-use crate::languages::LanguageAnalyzer;
+use crate::languages::{LanguageAnalyzer, push_unique};
+use crate::parser::SupportedLanguage;
 use crate::rules::AnalysisResult;
-use crate::rules::naming::{NamingConvention, Casing};
-use tree_sitter::Query;
+use crate::rules::naming::IdentifierKind;
+use crate::rules::dry::DuplicatedBlock;
+use crate::rules::error_handling::UnwrapUsage;
+use crate::rules::security::UnsafeBlock;
+use std::sync::LazyLock;
+use tree_sitter::{Language, Query};
 use streaming_iterator::StreamingIterator;
 
+const TECH_STACK_QUERY: &str = r#"
+    (use_declaration argument: (_) @import_source)
+"#;
+
+const NAMING_QUERY: &str = r#"
+    (function_item name: (identifier) @func_name)
+    (struct_item name: (type_identifier) @class_name)
+    (enum_item name: (type_identifier) @class_name)
+    (let_declaration pattern: (identifier) @var_name)
+    (const_item name: (identifier) @const_name)
+"#;
+
+const ERROR_HANDLING_QUERY: &str = r#"
+    (try_expression) @propagation
+    (macro_invocation macro: (identifier) @macro_name (#eq? @macro_name "panic")) @panic_call
+    (call_expression
+        function: (field_expression field: (field_identifier) @unwrap)
+        (#match? @unwrap "^(unwrap|expect)$")
+    )
+    (function_item
+        return_type: (generic_type type: [(type_identifier) (scoped_type_identifier)] @result_type)
+        (#match? @result_type "(^|::)Result$")
+    )
+    (enum_item name: (type_identifier) @error_enum (#match? @error_enum "Error$"))
+    (impl_item
+        trait: [(type_identifier) (scoped_type_identifier)] @error_trait
+        (#match? @error_trait "(^|::)Error$")
+    )
+    ((identifier) @error_crate (#match? @error_crate "^(anyhow|thiserror)$"))
+"#;
+
+const SAFETY_QUERY: &str = r#"
+    (unsafe_block) @unsafe_block
+    (impl_item "unsafe" trait: (type_identifier) @unsafe_impl (#match? @unsafe_impl "^(Send|Sync)$"))
+    (generic_type
+        type: (type_identifier) @arc
+        type_arguments: (type_arguments (generic_type type: [(type_identifier) (scoped_type_identifier)] @lock))
+        (#eq? @arc "Arc")
+        (#match? @lock "(^|::)(Mutex|RwLock)$")
+    )
+    (call_expression
+        function: (scoped_identifier path: (identifier) @arc)
+        arguments: (arguments (call_expression function: (scoped_identifier path: (_) @lock)))
+        (#eq? @arc "Arc")
+        (#match? @lock "(^|::)(Mutex|RwLock)$")
+    )
+    (call_expression
+        function: (scoped_identifier path: (_) @channel_module name: (identifier) @channel)
+        (#match? @channel_module "(^|::)(mpsc|broadcast|oneshot|watch|crossbeam_channel|flume)$")
+        (#match? @channel "^(channel|sync_channel|unbounded_channel|unbounded|bounded)$")
+    )
+    (trait_bounds (type_identifier) @send_sync (#match? @send_sync "^(Send|Sync)$"))
+    (bounded_type (type_identifier) @send_sync (#match? @send_sync "^(Send|Sync)$"))
+    ((type_identifier) @atomic (#match? @atomic "^Atomic[A-Z]"))
+"#;
+
+const DI_QUERY: &str = r#"
+    (dynamic_type) @trait_object
+    (type_parameters (type_parameter bounds: (trait_bounds))) @generic_bound
+    (where_clause) @generic_bound
+    (parameter type: (abstract_type)) @generic_bound
+"#;
+
+const LOGGING_QUERY: &str = r#"
+    (use_declaration argument: (_) @import)
+    (macro_invocation
+        macro: (identifier) @level
+        (#match? @level "^(trace|debug|info|warn|error|event)$")
+    ) @log_macro
+    (macro_invocation
+        macro: (scoped_identifier path: (identifier) @crate_name name: (identifier) @level)
+        (#match? @crate_name "^(log|tracing)$")
+        (#match? @level "^(trace|debug|info|warn|error|event)$")
+    ) @log_macro
+    (macro_invocation
+        macro: (identifier) @print
+        (#match? @print "^(println|eprintln|print|eprint|dbg)$")
+    ) @stray_print
+"#;
+
+const DRY_QUERY: &str = r#"
+    (string_literal) @string
+"#;
+
+pub(crate) struct RustQueries {
+    tech_stack: Query,
+    naming: Query,
+    error_handling: Query,
+    safety: Query,
+    di: Query,
+    logging: Query,
+    dry: Query,
+}
+
+impl RustQueries {
+    pub(crate) fn new(lang: &Language) -> Self {
+        Self {
+            tech_stack: Query::new(lang, TECH_STACK_QUERY).unwrap(),
+            naming: Query::new(lang, NAMING_QUERY).unwrap(),
+            error_handling: Query::new(lang, ERROR_HANDLING_QUERY).unwrap(),
+            safety: Query::new(lang, SAFETY_QUERY).unwrap(),
+            di: Query::new(lang, DI_QUERY).unwrap(),
+            logging: Query::new(lang, LOGGING_QUERY).unwrap(),
+            dry: Query::new(lang, DRY_QUERY).unwrap(),
+        }
+    }
+}
+
+static QUERIES: LazyLock<RustQueries> = LazyLock::new(|| RustQueries::new(&tree_sitter_rust::LANGUAGE.into()));
+
 pub struct RustAnalyzer;
 
 impl LanguageAnalyzer for RustAnalyzer {
     fn analyze(&self, content: &str, tree: &tree_sitter::Tree, result: &mut AnalysisResult) {
-        let ts_lang = tree_sitter_rust::LANGUAGE.into();
-        self.analyze_naming(content, tree, &ts_lang, result);
-        self.analyze_tech_stack(content, tree, &ts_lang, result);
-        self.analyze_error_handling(content, tree, &ts_lang, result);
-        self.analyze_dry(content, tree, &ts_lang, result);
+        let queries = &*QUERIES;
+        self.analyze_naming(content, tree, &queries.naming, result);
+        self.analyze_tech_stack(content, tree, &queries.tech_stack, result);
+        self.analyze_error_handling(content, tree, &queries.error_handling, result);
+        self.analyze_safety(content, tree, &queries.safety, result);
+        self.analyze_di(content, tree, &queries.di, result);
+        self.analyze_logging(content, tree, &queries.logging, result);
+        self.analyze_dry(content, tree, &queries.dry, result);
     }
 }
 
 impl RustAnalyzer {
-    fn analyze_tech_stack(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (use_declaration argument: (_) @import_source)
-        "#;
-        let query = Query::new(lang, query_str).unwrap();
+    fn analyze_tech_stack(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
 
         while let Some(m) = matches.next() {
             for capture in m.captures {
@@ -45,119 +159,336 @@ impl RustAnalyzer {
 
                 for (key, category, name) in techs {
                     if text.contains(key) {
-                        match category {
-                            "framework" => if !result.tech_stack.frameworks.contains(&name.to_string()) { result.tech_stack.frameworks.push(name.to_string()) },
-                            "database" => if !result.tech_stack.databases.contains(&name.to_string()) { result.tech_stack.databases.push(name.to_string()) },
-                            "library" => if !result.tech_stack.libraries.contains(&name.to_string()) { result.tech_stack.libraries.push(name.to_string()) },
-                            _ => {}
-                        }
+                        result.tech_stack.add(category, name);
                     }
                 }
             }
         }
     }
 
-    fn analyze_naming(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (function_item name: (identifier) @func_name)
-            (struct_item name: (type_identifier) @class_name)
-            (enum_item name: (type_identifier) @class_name)
-            (let_declaration pattern: (identifier) @var_name)
-            (const_item name: (identifier) @const_name)
-        "#;
-        
-        let query = Query::new(lang, query_str).unwrap();
+    fn analyze_naming(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
 
         while let Some(m) = matches.next() {
             for capture in m.captures {
                 let node = capture.node;
                 let name = &content[node.start_byte()..node.end_byte()];
-                let casing = NamingConvention::detect_casing(name);
+                let line = node.start_position().row + 1;
                 
                 let capture_name = query.capture_names()[capture.index as usize];
                 match capture_name {
-                    "func_name" => {
-                        if result.naming.function_casing == Casing::Unknown {
-                            result.naming.function_casing = casing;
-                        }
-                    },
-                    "class_name" => {
-                        if result.naming.class_struct_naming == Casing::Unknown {
-                            result.naming.class_struct_naming = casing;
-                        }
-                    },
-                    "var_name" => {
-                        if result.naming.variable_casing == Casing::Unknown {
-                            result.naming.variable_casing = casing;
-                        }
-                    },
+                    "func_name" => result.naming.record_identifier(IdentifierKind::Function, name, line),
+                    "class_name" => result.naming.record_identifier(IdentifierKind::Type, name, line),
+                    "var_name" => result.naming.record_identifier(IdentifierKind::Variable, name, line),
                     _ => {}
                 }
             }
         }
     }
 
-    fn analyze_error_handling(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (enum_variant name: (identifier) @variant_name)
-            (call_expression
-                function: (identifier) @func_name
-                (#match? @func_name "panic")
-            ) @panic_call
-        "#;
-        let query = Query::new(lang, query_str).unwrap();
+    fn analyze_error_handling(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
 
+        let mut usage = UnwrapUsage::default();
         while let Some(m) = matches.next() {
             for capture in m.captures {
+                let text = &content[capture.node.start_byte()..capture.node.end_byte()];
                 let capture_name = query.capture_names()[capture.index as usize];
-                match capture_name {
-                    "panic_call" => {
-                        let pattern = "panic!()".to_string();
-                        if !result.error_handling.failure_patterns.contains(&pattern) {
-                            result.error_handling.failure_patterns.push(pattern);
+                let pattern = match capture_name {
+                    "propagation" => "? propagation",
+                    "panic_call" => "panic!()",
+                    // Tests are expected to unwrap; only production code counts.
+                    "unwrap" if Self::in_test_code(capture.node, content) => continue,
+                    "unwrap" => {
+                        if text == "unwrap" {
+                            usage.unwrap_calls += 1;
+                        } else {
+                            usage.expect_calls += 1;
                         }
+                        "unwrap()/expect()"
                     },
-                    _ => {
-                        let name = &content[capture.node.start_byte()..capture.node.end_byte()];
-                        if name == "Result" || name == "Option" {
-                             let pattern = format!("Monadic ({})", name);
-                             if !result.error_handling.failure_patterns.contains(&pattern) {
-                                 result.error_handling.failure_patterns.push(pattern);
-                             }
-                        }
+                    "result_type" => "Result<T, E> returns",
+                    "error_enum" => "Custom error enums",
+                    "error_trait" => "Custom error types (impl Error)",
+                    "error_crate" if text == "thiserror" => "thiserror derives",
+                    "error_crate" => "anyhow (context-rich errors)",
+                    _ => continue,
+                };
+                push_unique(&mut result.error_handling.failure_patterns, pattern);
+            }
+        }
+
+        if usage.total() > 0 {
+            result.error_handling.unwrap_usage.push(usage);
+        }
+    }
+
+    /// Whether `node` sits in a `#[cfg(test)]` module or a `#[test]` function.
+    fn in_test_code(node: tree_sitter::Node, content: &str) -> bool {
+        let mut current = node.parent();
+        while let Some(item) = current {
+            if matches!(item.kind(), "mod_item" | "function_item") {
+                let mut sibling = item.prev_named_sibling();
+                while let Some(attribute) = sibling.filter(|s| s.kind() == "attribute_item") {
+                    let text = &content[attribute.start_byte()..attribute.end_byte()];
+                    if text == "#[test]" || text.contains("cfg(test)") || text.ends_with("::test]") {
+                        return true;
                     }
+                    sibling = attribute.prev_named_sibling();
                 }
             }
+            current = item.parent();
+        }
+        false
+    }
+
+    fn analyze_safety(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let text = &content[capture.node.start_byte()..capture.node.end_byte()];
+                let capture_name = query.capture_names()[capture.index as usize];
+                let primitive = match capture_name {
+                    "unsafe_block" => {
+                        let line = capture.node.start_position().row + 1;
+                        result.security.unsafe_blocks.push(UnsafeBlock { file: String::new(), line });
+                        continue;
+                    },
+                    "unsafe_impl" => "unsafe impl Send/Sync".to_string(),
+                    "lock" => format!("Arc<{}>", text.rsplit("::").next().unwrap_or(text)),
+                    "channel_module" => format!("Channels ({})", text.rsplit("::").next().unwrap_or(text)),
+                    "send_sync" => "Send/Sync bounds".to_string(),
+                    "atomic" => "Atomics".to_string(),
+                    _ => continue,
+                };
+                push_unique(&mut result.security.concurrency_primitives, &primitive);
+            }
         }
     }
 
-    fn analyze_dry(&self, content: &str, tree: &tree_sitter::Tree, lang: &tree_sitter::Language, result: &mut AnalysisResult) {
-        let query_str = r#"
-            (string_literal) @string
-        "#;
-        let query = Query::new(lang, query_str).unwrap();
+    fn analyze_di(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
         let mut cursor = tree_sitter::QueryCursor::new();
-        let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let capture_name = query.capture_names()[capture.index as usize];
+                let pattern = match capture_name {
+                    "trait_object" => "Trait Objects (dyn Trait)",
+                    "generic_bound" => "Generic Bounds (static dispatch)",
+                    _ => continue,
+                };
+                push_unique(&mut result.di.injection_patterns, pattern);
+            }
+        }
+    }
+
+    fn analyze_logging(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let text = |node: tree_sitter::Node| &content[node.start_byte()..node.end_byte()];
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+        let mut uses_tracing = false;
+        let mut calls = Vec::new();
+        let mut prints = Vec::new();
+        while let Some(m) = matches.next() {
+            let capture = |name: &str| m.captures.iter()
+                .find(|c| query.capture_names()[c.index as usize] == name)
+                .map(|c| c.node);
+            if let Some(import) = capture("import") {
+                uses_tracing |= text(import).starts_with("tracing");
+            } else if let (Some(call), Some(print)) = (capture("stray_print"), capture("print")) {
+                if !Self::in_test_code(call, content) {
+                    prints.push((text(print), call.start_position().row + 1));
+                }
+            } else if let Some(call) = capture("log_macro") {
+                // Bare `info!` comes from whichever crate the file imports it from;
+                // `#[macro_use] extern crate log` leaves no trace in the file.
+                let logger = match capture("crate_name").map(text) {
+                    Some(crate_name) => crate_name,
+                    None if uses_tracing => "tracing",
+                    None => "log",
+                };
+                calls.push((call, logger));
+            }
+        }
+
+        for (call, logger) in &calls {
+            // `info!(user_id = id, "loaded")` leads with fields, `info!("loaded {}", id)` with the format string.
+            let structured = call.named_children(&mut call.walk())
+                .find(|child| child.kind() == "token_tree")
+                .and_then(|tokens| tokens.named_child(0))
+                .is_some_and(|first| first.kind() != "string_literal");
+            result.error_handling.logging.record_call(logger, structured);
+        }
+        // Printing is how CLIs produce output, so `println!` only counts as
+        // stray next to log calls. `dbg!` always does.
+        for (print, line) in prints {
+            if print == "dbg" || !calls.is_empty() {
+                result.error_handling.logging.record_stray_print(&format!("{}!", print), line);
+            }
+        }
+    }
+
+    /// Turns what the files recorded into the repository-wide safety verdicts.
+    /// Does nothing for repositories without Rust code.
+    pub fn finalize(result: &mut AnalysisResult) {
+        if !result.languages.contains_key(&SupportedLanguage::Rust) {
+            return;
+        }
+
+        let security = &mut result.security;
+        security.unsafe_blocks.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        security.memory_safety = if security.unsafe_blocks.is_empty() {
+            "Safe Rust (no `unsafe` blocks)".to_string()
+        } else {
+            let mut files: Vec<&str> = security.unsafe_blocks.iter().map(|block| block.file.as_str()).collect();
+            files.dedup();
+            format!("{} `unsafe` block(s) in {} file(s)", security.unsafe_blocks.len(), files.len())
+        };
+        if !security.concurrency_primitives.is_empty() {
+            security.concurrency_safety = security.concurrency_primitives.join(", ");
+        }
+
+        let by_count = |a: &UnwrapUsage, b: &UnwrapUsage| b.total().cmp(&a.total()).then_with(|| a.file.cmp(&b.file));
+        result.error_handling.unwrap_usage.sort_by(by_count);
+        for findings in result.languages.values_mut() {
+            findings.error_handling.unwrap_usage.sort_by(by_count);
+        }
+    }
+
+    fn analyze_dry(&self, content: &str, tree: &tree_sitter::Tree, query: &Query, result: &mut AnalysisResult) {
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), content.as_bytes());
 
         let mut strings = std::collections::HashMap::new();
         while let Some(m) = matches.next() {
             for capture in m.captures {
                 let text = &content[capture.node.start_byte()..capture.node.end_byte()];
                 if text.len() > 10 {
-                    *strings.entry(text).or_insert(0) += 1;
+                    strings.entry(text).or_insert((0, capture.node.start_position().row + 1)).0 += 1;
                 }
             }
         }
 
-        for (text, count) in strings {
+        for (text, (count, line)) in strings {
             if count > 1 {
-                result.dry.duplicated_blocks.push(format!("String literal repeated {} times: {}", count, text));
-                result.dry.duplication_score += (count - 1) as f64 * 0.1;
+                result.dry.duplicated_blocks.push(DuplicatedBlock {
+                    file: String::new(),
+                    line,
+                    description: format!("String literal repeated {} times: {}", count, text),
+                });
+                result.dry.repeated_literals += count - 1;
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tree_sitter::Parser;
+
+    const SERVICE: &str = r#"
+use std::sync::{mpsc, Arc, Mutex};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StoreError {
+    #[error("not found")]
+    NotFound,
+}
+
+pub struct Service {
+    store: Box<dyn Store>,
+    hits: Arc<Mutex<u64>>,
+}
+
+impl Service {
+    pub fn load(&self, key: &str) -> Result<Vec<u8>, StoreError> {
+        let value = self.store.get(key)?;
+        *self.hits.lock().unwrap() += 1;
+        let (tx, _rx) = mpsc::channel();
+        tx.send(value.len()).expect("receiver outlives the service");
+        Ok(unsafe { decode(&value) })
+    }
+}
+
+pub fn spawn<T: Send + 'static>(task: T) {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn loads() {
+        service().load("key").unwrap();
+    }
+}
+"#;
+
+    #[test]
+    fn test_rust_error_handling_safety_and_di() {
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_rust::LANGUAGE.into()).unwrap();
+        let tree = parser.parse(SERVICE, None).unwrap();
+        let mut result = AnalysisResult::default();
+        RustAnalyzer.analyze(SERVICE, &tree, &mut result);
+
+        let patterns = &result.error_handling.failure_patterns;
+        for expected in ["? propagation", "unwrap()/expect()", "Result<T, E> returns", "Custom error enums", "thiserror derives"] {
+            assert!(patterns.contains(&expected.to_string()), "missing {}", expected);
+        }
+        assert_eq!(result.error_handling.unwrap_usage, vec![UnwrapUsage { file: String::new(), unwrap_calls: 1, expect_calls: 1 }]);
+        assert_eq!(result.security.unsafe_blocks.len(), 1);
+        assert_eq!(result.security.concurrency_primitives, vec!["Arc<Mutex>", "Channels (mpsc)", "Send/Sync bounds"]);
+        assert_eq!(result.di.injection_patterns, vec!["Trait Objects (dyn Trait)", "Generic Bounds (static dispatch)"]);
+    }
+
+    #[test]
+    fn test_rust_logging_analysis() {
+        let content = r#"
+use tracing::{info, warn};
+
+fn sync(id: u64) {
+    info!(id, "syncing");
+    warn!("slow sync for {}", id);
+    log::error!("legacy path");
+    println!("done");
+}
+
+fn main() {
+    println!("usage: sync <id>");
+    dbg!(42);
+}
+"#;
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_rust::LANGUAGE.into()).unwrap();
+        let tree = parser.parse(content, None).unwrap();
+        let mut result = AnalysisResult::default();
+        RustAnalyzer.analyze(content, &tree, &mut result);
+
+        let logging = &result.error_handling.logging;
+        assert_eq!(logging.logger_calls.get("tracing"), Some(&2));
+        assert_eq!(logging.logger_calls.get("log"), Some(&1));
+        assert_eq!((logging.structured_calls, logging.printf_calls), (1, 2));
+        let prints: Vec<_> = logging.stray_prints.iter().map(|p| (p.call.as_str(), p.line)).collect();
+        assert_eq!(prints, vec![("println!", 8), ("println!", 12), ("dbg!", 13)]);
+    }
+
+    #[test]
+    fn test_finalize_sets_safety_verdicts() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/service.rs"), SERVICE).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "pub mod service;\n").unwrap();
+        let result = crate::analyzer::ProjectAnalyzer::new(dir.path()).analyze().unwrap();
+
+        assert_eq!(result.security.memory_safety, "1 `unsafe` block(s) in 1 file(s)");
+        assert_eq!(result.security.unsafe_blocks[0].file, "src/service.rs");
+        assert_eq!(result.security.concurrency_safety, "Arc<Mutex>, Channels (mpsc), Send/Sync bounds");
+        assert_eq!(result.error_handling.unwrap_usage[0].file, "src/service.rs");
+    }
+}
@@ -4,5 +4,16 @@ pub mod report;
 pub mod parser;
 pub mod languages;
 pub mod llm;
+pub mod i18n;
+pub mod sarif;
+pub mod secrets;
+pub mod manifests;
+pub mod scripts;
+pub mod clones;
+pub mod cache;
+pub mod git_diff;
+pub mod config;
+pub mod custom_rules;
+pub mod plugins;
 
 pub use analyzer::ProjectAnalyzer;
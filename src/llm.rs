@@ -1,7 +1,90 @@
 use serde::{Serialize, Deserialize};
+use crate::i18n::Locale;
 use crate::rules::AnalysisResult;
 use anyhow::{Context, Result};
 
+/// A chat-style model that can turn an analysis result into written insights.
+pub trait LlmProvider {
+    /// Human-readable provider name used in progress messages.
+    fn name(&self) -> String;
+
+    /// Sends a single user prompt and returns the model's text reply.
+    fn complete(&self, prompt: &str) -> Result<String>;
+
+    fn analyze(&self, result: &AnalysisResult, locale: Locale) -> Result<String> {
+        self.complete(&build_prompt(result, locale))
+    }
+}
+
+#[derive(clap::ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmProviderKind {
+    /// Google Gemini (requires GEMINI_API_KEY)
+    #[default]
+    Gemini,
+    /// Any OpenAI-compatible chat-completions endpoint (OPENAI_API_KEY is optional)
+    Openai,
+    /// A local Ollama server
+    Ollama,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LlmConfig {
+    pub provider: LlmProviderKind,
+    pub model: Option<String>,
+    pub base_url: Option<String>,
+}
+
+pub fn create_provider(config: &LlmConfig) -> Result<Box<dyn LlmProvider>> {
+    let model = config.model.clone();
+    let base_url = config.base_url.clone();
+    Ok(match config.provider {
+        LlmProviderKind::Gemini => Box::new(GeminiClient::new(model, base_url)?),
+        LlmProviderKind::Openai => Box::new(OpenAiCompatibleClient::new(model, base_url)),
+        LlmProviderKind::Ollama => Box::new(OllamaClient::new(model, base_url)),
+    })
+}
+
+fn build_prompt(result: &AnalysisResult, locale: Locale) -> String {
+    let result_json = serde_json::to_string_pretty(result).unwrap_or_default();
+    format!(
+        "You are an expert software architect. Analyze the following repository analysis results and provide professional insights, context, and best practice recommendations for each aspect.
+        The goal is to create a high-quality guideline.md for an AI agent.
+
+        Analysis Results (JSON):
+        ```json
+        {}
+        ```
+
+        Please provide the output in Markdown format with the following sections if applicable:
+        - Executive Summary
+        - Detailed Analysis Insights (Naming, DI, Testing, Config, Security, Error Handling, Design Patterns)
+        - Strategic Recommendations, you can add sample code snippets if applicable.
+
+        Use {} language for the analysis and recommendations.",
+        result_json,
+        locale.language_name()
+    )
+}
+
+fn post_json<T: Serialize, R: for<'de> Deserialize<'de>>(
+    provider: &str,
+    request: reqwest::blocking::RequestBuilder,
+    body: &T,
+) -> Result<R> {
+    let response = request.json(body).send()?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().unwrap_or_else(|_| "Could not read error body".to_string());
+        anyhow::bail!("{} API error ({}): {}", provider, status, error_text);
+    }
+
+    Ok(response.json()?)
+}
+
+// --- Gemini ---
+
 #[derive(Serialize)]
 struct GeminiRequest {
     contents: Vec<Content>,
@@ -39,71 +122,241 @@ struct ResponsePart {
 
 pub struct GeminiClient {
     api_key: String,
+    model: String,
+    base_url: String,
 }
 
 impl GeminiClient {
-    pub fn new() -> Result<Self> {
+    pub const DEFAULT_MODEL: &'static str = "gemini-3-flash-preview";
+    pub const DEFAULT_BASE_URL: &'static str = "https://generativelanguage.googleapis.com/v1beta";
+
+    pub fn new(model: Option<String>, base_url: Option<String>) -> Result<Self> {
         let api_key = std::env::var("GEMINI_API_KEY")
             .context("GEMINI_API_KEY environment variable not set")?;
-        Ok(Self { api_key })
+        Ok(Self {
+            api_key,
+            model: model.unwrap_or_else(|| Self::DEFAULT_MODEL.to_string()),
+            base_url: base_url.unwrap_or_else(|| Self::DEFAULT_BASE_URL.to_string()),
+        })
+    }
+}
+
+impl LlmProvider for GeminiClient {
+    fn name(&self) -> String {
+        format!("Google Gemini, {}", self.model)
     }
 
-    pub fn analyze(&self, result: &AnalysisResult) -> Result<String> {
+    fn complete(&self, prompt: &str) -> Result<String> {
         let client = reqwest::blocking::Client::new();
         let url = format!(
-            "https://generativelanguage.googleapis.com/v1beta/models/gemini-3-flash-preview:generateContent?key={}",
+            "{}/models/{}:generateContent?key={}",
+            self.base_url.trim_end_matches('/'),
+            self.model,
             self.api_key
         );
 
-        let prompt = self.build_prompt(result);
-
         let request = GeminiRequest {
             contents: vec![Content {
-                parts: vec![Part { text: prompt }],
+                parts: vec![Part { text: prompt.to_string() }],
             }],
         };
 
-        let response = client
-            .post(&url)
-            .json(&request)
-            .send()?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().unwrap_or_else(|_| "Could not read error body".to_string());
-            anyhow::bail!("Gemini API error ({}): {}", status, error_text);
-        }
-
-        let gemini_response: GeminiResponse = response.json()?;
+        let gemini_response: GeminiResponse = post_json("Gemini", client.post(&url), &request)?;
 
-        let text = gemini_response
+        gemini_response
             .candidates
             .first()
             .and_then(|c| c.content.parts.first())
             .map(|p| p.text.clone())
-            .context("Failed to get response text from Gemini")?;
+            .context("Failed to get response text from Gemini")
+    }
+}
+
+// --- OpenAI-compatible chat completions ---
+
+#[derive(Serialize, Deserialize)]
+struct ChatMessage {
+    role: String,
+    content: String,
+}
+
+#[derive(Serialize)]
+struct ChatCompletionRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+pub struct OpenAiCompatibleClient {
+    api_key: Option<String>,
+    model: String,
+    base_url: String,
+}
 
-        Ok(text)
+impl OpenAiCompatibleClient {
+    pub const DEFAULT_MODEL: &'static str = "gpt-4o-mini";
+    pub const DEFAULT_BASE_URL: &'static str = "https://api.openai.com/v1";
+
+    /// Self-hosted endpoints often need no key, so `OPENAI_API_KEY` is optional.
+    pub fn new(model: Option<String>, base_url: Option<String>) -> Self {
+        Self {
+            api_key: std::env::var("OPENAI_API_KEY").ok(),
+            model: model.unwrap_or_else(|| Self::DEFAULT_MODEL.to_string()),
+            base_url: base_url.unwrap_or_else(|| Self::DEFAULT_BASE_URL.to_string()),
+        }
+    }
+}
+
+impl LlmProvider for OpenAiCompatibleClient {
+    fn name(&self) -> String {
+        format!("OpenAI-compatible, {}", self.model)
     }
 
-    fn build_prompt(&self, result: &AnalysisResult) -> String {
-        let result_json = serde_json::to_string_pretty(result).unwrap_or_default();
-        format!(
-            "You are an expert software architect. Analyze the following repository analysis results and provide professional insights, context, and best practice recommendations for each aspect. 
-            The goal is to create a high-quality guideline.md for an AI agent.
-            
-            Analysis Results (JSON):
-            ```json
-            {}
-            ```
-            
-            Please provide the output in Markdown format with the following sections if applicable:
-            - Executive Summary
-            - Detailed Analysis Insights (Naming, DI, Testing, Config, Security, Error Handling, Design Patterns)
-            - Strategic Recommendations, you can add sample code snippets if applicable.
-            
-            Use Indonesian language for the analysis and recommendations.",
-            result_json
-        )
+    fn complete(&self, prompt: &str) -> Result<String> {
+        let client = reqwest::blocking::Client::new();
+        let url = format!("{}/chat/completions", self.base_url.trim_end_matches('/'));
+
+        let mut builder = client.post(&url);
+        if let Some(api_key) = &self.api_key {
+            builder = builder.bearer_auth(api_key);
+        }
+
+        let request = ChatCompletionRequest {
+            model: self.model.clone(),
+            messages: vec![ChatMessage { role: "user".to_string(), content: prompt.to_string() }],
+        };
+
+        let response: ChatCompletionResponse = post_json("OpenAI-compatible", builder, &request)?;
+
+        response
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content)
+            .context("Failed to get response text from OpenAI-compatible endpoint")
+    }
+}
+
+// --- Ollama ---
+
+#[derive(Serialize)]
+struct OllamaChatRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    stream: bool,
+}
+
+#[derive(Deserialize)]
+struct OllamaChatResponse {
+    message: ChatMessage,
+}
+
+pub struct OllamaClient {
+    model: String,
+    base_url: String,
+}
+
+impl OllamaClient {
+    pub const DEFAULT_MODEL: &'static str = "llama3.1";
+    pub const DEFAULT_BASE_URL: &'static str = "http://localhost:11434";
+
+    pub fn new(model: Option<String>, base_url: Option<String>) -> Self {
+        Self {
+            model: model.unwrap_or_else(|| Self::DEFAULT_MODEL.to_string()),
+            base_url: base_url.unwrap_or_else(|| Self::DEFAULT_BASE_URL.to_string()),
+        }
+    }
+}
+
+impl LlmProvider for OllamaClient {
+    fn name(&self) -> String {
+        format!("Ollama, {}", self.model)
+    }
+
+    fn complete(&self, prompt: &str) -> Result<String> {
+        let client = reqwest::blocking::Client::new();
+        let url = format!("{}/api/chat", self.base_url.trim_end_matches('/'));
+
+        let request = OllamaChatRequest {
+            model: self.model.clone(),
+            messages: vec![ChatMessage { role: "user".to_string(), content: prompt.to_string() }],
+            stream: false,
+        };
+
+        let response: OllamaChatResponse = post_json("Ollama", client.post(&url), &request)?;
+        Ok(response.message.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serves a single HTTP request with `body` and returns the base URL plus
+    /// a handle yielding the raw request that was received.
+    fn mock_server(body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let content_length = text[..header_end]
+                        .lines()
+                        .find_map(|l| l.to_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap()))
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + content_length {
+                        break;
+                    }
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            String::from_utf8_lossy(&request).to_string()
+        });
+        (format!("http://{}", addr), handle)
+    }
+
+    #[test]
+    fn test_openai_compatible_against_mock_server() {
+        let (base_url, handle) = mock_server(r#"{"choices":[{"message":{"role":"assistant","content":"insights"}}]}"#);
+        let client = OpenAiCompatibleClient::new(Some("local-model".to_string()), Some(base_url));
+
+        assert_eq!(client.complete("hello").unwrap(), "insights");
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("POST /chat/completions"));
+        assert!(request.contains(r#""model":"local-model""#));
+    }
+
+    #[test]
+    fn test_ollama_against_mock_server() {
+        let (base_url, handle) = mock_server(r#"{"message":{"role":"assistant","content":"ollama insights"}}"#);
+        let client = OllamaClient::new(None, Some(base_url));
+
+        assert_eq!(client.complete("hello").unwrap(), "ollama insights");
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("POST /api/chat"));
+        assert!(request.contains(r#""stream":false"#));
     }
 }
@@ -1,8 +1,15 @@
 
 use std::path::Path;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use buddy::ProjectAnalyzer;
 use buddy::report::ReportGenerator;
+use buddy::sarif::SarifGenerator;
+use buddy::llm::{LlmConfig, LlmProviderKind};
+use buddy::i18n::Locale;
+use buddy::secrets::{SecretsBaseline, BASELINE_FILE};
+use buddy::config::{BuddyConfig, CONFIG_FILE};
+use buddy::custom_rules::CustomRuleSet;
+use buddy::plugins::PluginRegistry;
 use std::fs;
 
 #[derive(Parser, Debug)]
@@ -12,13 +19,85 @@ struct Args {
     #[arg(default_value = ".")]
     path: String,
 
-    /// Output file name
-    #[arg(short, long, default_value = "CLAUDE.md")]
-    output: String,
+    /// Output file name [default: CLAUDE.md, buddy-report.json or buddy.sarif depending on --format]
+    #[arg(short, long)]
+    output: Option<String>,
 
-    /// Use LLM (Google Gemini) for more accurate analysis
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Markdown)]
+    format: OutputFormat,
+
+    /// Settings file [default: .buddy.toml in the analyzed directory]
+    #[arg(long, value_name = "FILE")]
+    config: Option<String>,
+
+    /// Accept all current secret findings into .buddy-secrets-baseline.json and exit. With --since, entries of unchanged files are kept
+    #[arg(long, default_value_t = false)]
+    update_baseline: bool,
+
+    /// Load language plugins whose grammar is a shared library. Loading runs the library's code, so only use this on trusted repositories
+    #[arg(long, default_value_t = false)]
+    allow_plugin_libraries: bool,
+
+    /// Re-analyze every file instead of reusing unchanged results from .buddy/cache
+    #[arg(long, default_value_t = false)]
+    no_cache: bool,
+
+    /// Only report findings in files changed since this git ref (e.g. origin/main), judged against whole-repo conventions
+    #[arg(long, value_name = "GIT_REF")]
+    since: Option<String>,
+
+    /// List at most this many naming violations in the report
+    #[arg(long, value_name = "N")]
+    max_violations: Option<usize>,
+
+    /// Exit with status 1 when any naming violation is found, for use as a CI check
+    #[arg(long, default_value_t = false)]
+    fail_on_violations: bool,
+
+    /// Use an LLM for more accurate analysis
     #[arg(long, default_value_t = false)]
     with_llm: bool,
+
+    /// Don't use an LLM, even when the file passed with --config enables it
+    #[arg(long, default_value_t = false, conflicts_with = "with_llm")]
+    no_llm: bool,
+
+    /// LLM provider used with --with-llm [default: gemini]
+    #[arg(long, value_enum)]
+    llm_provider: Option<LlmProviderKind>,
+
+    /// Language for report context text and LLM insights
+    #[arg(long, value_enum, default_value_t = Locale::En)]
+    lang: Locale,
+
+    /// Model name passed to the LLM provider [default: provider specific]
+    #[arg(long)]
+    llm_model: Option<String>,
+
+    /// Base URL of the LLM API, e.g. a self-hosted OpenAI-compatible server
+    #[arg(long)]
+    llm_base_url: Option<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum OutputFormat {
+    /// CLAUDE.md guideline for AI agents
+    Markdown,
+    /// The full analysis result
+    Json,
+    /// Secrets, duplication and naming violations for code-scanning tools
+    Sarif,
+}
+
+impl OutputFormat {
+    fn default_output(&self) -> &'static str {
+        match self {
+            OutputFormat::Markdown => "CLAUDE.md",
+            OutputFormat::Json => "buddy-report.json",
+            OutputFormat::Sarif => "buddy.sarif",
+        }
+    }
 }
 
 fn main() -> anyhow::Result<()> {
@@ -30,16 +109,63 @@ fn main() -> anyhow::Result<()> {
         anyhow::bail!("Path does not exist: {}", args.path);
     }
 
+    let config = match &args.config {
+        Some(file) => BuddyConfig::from_file(Path::new(file))?,
+        None => {
+            let mut config = BuddyConfig::load(path)?;
+            let ignored = config.llm.restrict_to_repository();
+            if !ignored.is_empty() {
+                eprintln!("Ignoring llm.{} in {}: use the command line or a file passed with --config", ignored.join(", llm."), CONFIG_FILE);
+            }
+            config
+        }
+    };
+
+    // Plugins go first: custom rules may target their languages.
+    let mut plugins = PluginRegistry::default().with_libraries(args.allow_plugin_libraries);
+    let plugin_languages = plugins.load_dir(&path.join(config.languages_dir()))?;
+    let custom_rules = CustomRuleSet::load(&path.join(config.rules_dir()), &plugins)?;
+
     println!("Analyzing repository at: {:?}", path);
+    if !plugin_languages.is_empty() {
+        let names: Vec<&str> = plugin_languages.iter().map(|language| language.as_str()).collect();
+        println!("Loaded language plugin(s): {}", names.join(", "));
+    }
+    if !custom_rules.is_empty() {
+        println!("Loaded {} custom rule(s) from {}", custom_rules.len(), config.rules_dir());
+    }
 
-    let analyzer = ProjectAnalyzer::new(path);
+    let analyzer = ProjectAnalyzer::new(path)
+        .with_cache(!args.no_cache)
+        .with_since(args.since.clone())
+        .with_config(config.clone())
+        .with_custom_rules(custom_rules)
+        .with_plugins(plugins);
     let mut result = analyzer.analyze()?;
 
-    if args.with_llm {
-        println!("Enhancing analysis with LLM (Google Gemini)...");
-        match buddy::llm::GeminiClient::new() {
+    if args.update_baseline {
+        let mut baseline = SecretsBaseline::load(path)?;
+        baseline.update(&result.security.hardcoded_secrets, result.diff.as_ref().map(|diff| diff.changed_files.as_slice()));
+        baseline.save(path)?;
+        println!("Secrets baseline updated with {} finding(s) at: {}", baseline.findings.len(), path.join(BASELINE_FILE).display());
+        return Ok(());
+    }
+    result.security.baselined_secrets = SecretsBaseline::load(path)?.filter(&mut result.security.hardcoded_secrets);
+    if let Some(max) = args.max_violations {
+        result.naming_violations.truncate(max);
+    }
+
+    // Command-line flags take precedence over `.buddy.toml`.
+    if !args.no_llm && (args.with_llm || config.llm.enabled) {
+        let llm_config = LlmConfig {
+            provider: args.llm_provider.or(config.llm.provider).unwrap_or_default(),
+            model: args.llm_model.clone().or_else(|| config.llm.model.clone()),
+            base_url: args.llm_base_url.clone().or_else(|| config.llm.base_url.clone()),
+        };
+        match buddy::llm::create_provider(&llm_config) {
             Ok(client) => {
-                match client.analyze(&result) {
+                println!("Enhancing analysis with LLM ({})...", client.name());
+                match client.analyze(&result, args.lang) {
                     Ok(summary) => {
                         result.llm_summary = Some(summary);
                         println!("LLM enhancement completed.");
@@ -51,10 +177,20 @@ fn main() -> anyhow::Result<()> {
         }
     }
 
-    let report = ReportGenerator::generate(&result);
+    let output = args.output.as_deref().unwrap_or(args.format.default_output());
+    let report = match args.format {
+        OutputFormat::Markdown => ReportGenerator::generate_sections(&result, args.lang, config.report_sections()),
+        OutputFormat::Json => serde_json::to_string_pretty(&result)?,
+        OutputFormat::Sarif => SarifGenerator::generate(&result)?,
+    };
     
-    fs::write(&args.output, report)?;
-    println!("Guideline generated successfully at: {}", args.output);
+    fs::write(output, report)?;
+    println!("Guideline generated successfully at: {}", output);
+
+    if args.fail_on_violations && !result.naming_violations.is_empty() {
+        eprintln!("Found {} naming convention violation(s)", result.naming_violations.total);
+        std::process::exit(1);
+    }
 
     Ok(())
 }
@@ -0,0 +1,435 @@
+use crate::config::TechSignature;
+use crate::rules::tech_stack::TechStack;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ecosystem {
+    Go,
+    Cargo,
+    Npm,
+    PyPI,
+}
+
+/// (ecosystem, package name, category, display name). Go entries match by
+/// module path prefix so major-version suffixes like `/v5` still match.
+const SIGNATURES: &[(Ecosystem, &str, &str, &str)] = &[
+    (Ecosystem::Go, "github.com/gin-gonic/gin", "framework", "Gin"),
+    (Ecosystem::Go, "github.com/labstack/echo", "framework", "Echo"),
+    (Ecosystem::Go, "github.com/gofiber/fiber", "framework", "Fiber"),
+    (Ecosystem::Go, "github.com/go-chi/chi", "framework", "chi"),
+    (Ecosystem::Go, "gorm.io/gorm", "database", "GORM"),
+    (Ecosystem::Go, "github.com/jmoiron/sqlx", "database", "sqlx"),
+    (Ecosystem::Go, "github.com/jackc/pgx", "database", "pgx"),
+    (Ecosystem::Go, "github.com/lib/pq", "database", "PostgreSQL (lib/pq)"),
+    (Ecosystem::Go, "github.com/go-sql-driver/mysql", "database", "MySQL"),
+    (Ecosystem::Go, "github.com/redis/go-redis", "database", "Redis"),
+    (Ecosystem::Go, "go.mongodb.org/mongo-driver", "database", "MongoDB"),
+    (Ecosystem::Go, "google.golang.org/grpc", "library", "gRPC"),
+    (Ecosystem::Go, "github.com/spf13/cobra", "library", "Cobra"),
+    (Ecosystem::Go, "github.com/spf13/viper", "library", "Viper"),
+    (Ecosystem::Go, "github.com/stretchr/testify", "library", "testify"),
+    (Ecosystem::Go, "go.uber.org/zap", "library", "zap"),
+    (Ecosystem::Cargo, "axum", "framework", "Axum"),
+    (Ecosystem::Cargo, "actix-web", "framework", "Actix-web"),
+    (Ecosystem::Cargo, "rocket", "framework", "Rocket"),
+    (Ecosystem::Cargo, "sqlx", "database", "sqlx"),
+    (Ecosystem::Cargo, "diesel", "database", "Diesel"),
+    (Ecosystem::Cargo, "sea-orm", "database", "SeaORM"),
+    (Ecosystem::Cargo, "redis", "database", "Redis"),
+    (Ecosystem::Cargo, "tokio", "library", "Tokio"),
+    (Ecosystem::Cargo, "serde", "library", "Serde"),
+    (Ecosystem::Cargo, "reqwest", "library", "Reqwest"),
+    (Ecosystem::Cargo, "anyhow", "library", "Anyhow"),
+    (Ecosystem::Cargo, "thiserror", "library", "thiserror"),
+    (Ecosystem::Cargo, "clap", "library", "Clap"),
+    (Ecosystem::Cargo, "tracing", "library", "tracing"),
+    (Ecosystem::Cargo, "rayon", "library", "Rayon"),
+    (Ecosystem::Cargo, "tree-sitter", "library", "tree-sitter"),
+    (Ecosystem::Npm, "express", "framework", "Express"),
+    (Ecosystem::Npm, "next", "framework", "Next.js"),
+    (Ecosystem::Npm, "@nestjs/core", "framework", "NestJS"),
+    (Ecosystem::Npm, "fastify", "framework", "Fastify"),
+    (Ecosystem::Npm, "koa", "framework", "Koa"),
+    (Ecosystem::Npm, "nuxt", "framework", "Nuxt"),
+    (Ecosystem::Npm, "@angular/core", "framework", "Angular"),
+    (Ecosystem::Npm, "react", "library", "React"),
+    (Ecosystem::Npm, "vue", "library", "Vue"),
+    (Ecosystem::Npm, "svelte", "library", "Svelte"),
+    (Ecosystem::Npm, "axios", "library", "Axios"),
+    (Ecosystem::Npm, "jest", "library", "Jest"),
+    (Ecosystem::Npm, "vitest", "library", "Vitest"),
+    (Ecosystem::Npm, "mocha", "library", "Mocha"),
+    (Ecosystem::Npm, "mongoose", "database", "Mongoose"),
+    (Ecosystem::Npm, "prisma", "database", "Prisma"),
+    (Ecosystem::Npm, "@prisma/client", "database", "Prisma"),
+    (Ecosystem::Npm, "typeorm", "database", "TypeORM"),
+    (Ecosystem::Npm, "sequelize", "database", "Sequelize"),
+    (Ecosystem::Npm, "pg", "database", "PostgreSQL (pg)"),
+    (Ecosystem::Npm, "redis", "database", "Redis"),
+    (Ecosystem::Npm, "ioredis", "database", "Redis"),
+    (Ecosystem::Npm, "typescript", "build_tool", "TypeScript compiler"),
+    (Ecosystem::Npm, "vite", "build_tool", "Vite"),
+    (Ecosystem::Npm, "webpack", "build_tool", "webpack"),
+    (Ecosystem::Npm, "esbuild", "build_tool", "esbuild"),
+    (Ecosystem::PyPI, "django", "framework", "Django"),
+    (Ecosystem::PyPI, "flask", "framework", "Flask"),
+    (Ecosystem::PyPI, "fastapi", "framework", "FastAPI"),
+    (Ecosystem::PyPI, "sqlalchemy", "database", "SQLAlchemy"),
+    (Ecosystem::PyPI, "psycopg2", "database", "PostgreSQL (psycopg2)"),
+    (Ecosystem::PyPI, "psycopg2-binary", "database", "PostgreSQL (psycopg2)"),
+    (Ecosystem::PyPI, "psycopg", "database", "PostgreSQL (psycopg)"),
+    (Ecosystem::PyPI, "pymongo", "database", "MongoDB"),
+    (Ecosystem::PyPI, "redis", "database", "Redis"),
+    (Ecosystem::PyPI, "pandas", "library", "Pandas"),
+    (Ecosystem::PyPI, "numpy", "library", "NumPy"),
+    (Ecosystem::PyPI, "pydantic", "library", "Pydantic"),
+    (Ecosystem::PyPI, "requests", "library", "Requests"),
+    (Ecosystem::PyPI, "celery", "library", "Celery"),
+    (Ecosystem::PyPI, "pytest", "library", "pytest"),
+];
+
+/// Lockfiles and manifests that identify the package manager or build tool.
+const BUILD_TOOL_FILES: &[(&str, &str)] = &[
+    ("package-lock.json", "npm"),
+    ("yarn.lock", "Yarn"),
+    ("pnpm-lock.yaml", "pnpm"),
+    ("bun.lockb", "Bun"),
+    ("bun.lock", "Bun"),
+    ("poetry.lock", "Poetry"),
+    ("uv.lock", "uv"),
+    ("Pipfile.lock", "Pipenv"),
+    ("Pipfile", "Pipenv"),
+    ("Cargo.toml", "Cargo"),
+    ("Cargo.lock", "Cargo"),
+    ("go.mod", "Go modules"),
+    ("pom.xml", "Maven"),
+    ("build.gradle", "Gradle"),
+    ("build.gradle.kts", "Gradle"),
+];
+
+/// Fills `TechStack` from dependency manifests (`go.mod`, `Cargo.toml`,
+/// `package.json`, `requirements.txt`, `pyproject.toml`, `Pipfile`) and
+/// detects package managers from lockfiles.
+pub struct ManifestAnalyzer;
+
+impl ManifestAnalyzer {
+    /// Returns true when `file_name` is a manifest or lockfile this analyzer understands.
+    pub fn is_manifest(file_name: &str) -> bool {
+        BUILD_TOOL_FILES.iter().any(|(name, _)| *name == file_name)
+            || matches!(file_name, "package.json" | "pyproject.toml")
+            || Self::is_requirements_file(file_name)
+    }
+
+    pub fn analyze(file_name: &str, content: &str, tech_stack: &mut TechStack) {
+        Self::analyze_with_signatures(file_name, content, &[], tech_stack);
+    }
+
+    /// Like `analyze`, also recognizing the project's own `extra` signatures
+    /// from `.buddy.toml`. They win over built-in ones for the same package.
+    pub fn analyze_with_signatures(file_name: &str, content: &str, extra: &[TechSignature], tech_stack: &mut TechStack) {
+        for (name, tool) in BUILD_TOOL_FILES {
+            if *name == file_name {
+                tech_stack.add("build_tool", tool);
+            }
+        }
+
+        let dependencies = match file_name {
+            "go.mod" => Self::parse_go_mod(content),
+            "Cargo.toml" => Self::parse_cargo_toml(content),
+            "package.json" => Self::parse_package_json(content, tech_stack),
+            "pyproject.toml" => Self::parse_pyproject(content, tech_stack),
+            "Pipfile" => Self::parse_pipfile(content),
+            name if Self::is_requirements_file(name) => Self::parse_requirements(content),
+            _ => return,
+        };
+
+        let ecosystem = match file_name {
+            "go.mod" => Ecosystem::Go,
+            "Cargo.toml" => Ecosystem::Cargo,
+            "package.json" => Ecosystem::Npm,
+            _ => Ecosystem::PyPI,
+        };
+
+        for (package, version) in dependencies {
+            let custom = extra.iter()
+                .find(|signature| signature.matches(&package))
+                .map(|signature| (signature.category.as_str(), signature.name.as_str()));
+            if let Some((category, display)) = custom.or_else(|| Self::lookup(ecosystem, &package)) {
+                tech_stack.add_versioned(category, display, version.as_deref());
+            }
+        }
+    }
+
+    fn is_requirements_file(file_name: &str) -> bool {
+        file_name.starts_with("requirements") && file_name.ends_with(".txt")
+    }
+
+    fn lookup(ecosystem: Ecosystem, package: &str) -> Option<(&'static str, &'static str)> {
+        SIGNATURES.iter()
+            .find(|(eco, name, _, _)| {
+                *eco == ecosystem && match ecosystem {
+                    Ecosystem::Go => package == *name || package.starts_with(&format!("{}/", name)),
+                    Ecosystem::PyPI => package.eq_ignore_ascii_case(name) || package.replace('_', "-").eq_ignore_ascii_case(name),
+                    _ => package == *name,
+                }
+            })
+            .map(|(_, _, category, display)| (*category, *display))
+    }
+
+    fn parse_go_mod(content: &str) -> Vec<(String, Option<String>)> {
+        let mut deps = Vec::new();
+        let mut in_require_block = false;
+        for line in content.lines() {
+            let line = line.split("//").next().unwrap_or("").trim();
+            let spec = if in_require_block {
+                if line == ")" {
+                    in_require_block = false;
+                    continue;
+                }
+                line
+            } else if line == "require (" {
+                in_require_block = true;
+                continue;
+            } else if let Some(rest) = line.strip_prefix("require ") {
+                rest.trim()
+            } else {
+                continue;
+            };
+
+            let mut parts = spec.split_whitespace();
+            if let (Some(module), Some(version)) = (parts.next(), parts.next()) {
+                deps.push((module.to_string(), Some(version.to_string())));
+            }
+        }
+        deps
+    }
+
+    fn parse_cargo_toml(content: &str) -> Vec<(String, Option<String>)> {
+        let Ok(value) = content.parse::<toml::Table>() else { return Vec::new() };
+        let mut deps = Vec::new();
+        let mut collect = |table: Option<&toml::Value>| {
+            let Some(table) = table.and_then(|t| t.as_table()) else { return };
+            for (name, spec) in table {
+                let package = spec.get("package").and_then(|p| p.as_str()).unwrap_or(name);
+                let version = match spec {
+                    toml::Value::String(v) => Some(v.clone()),
+                    toml::Value::Table(t) => t.get("version").and_then(|v| v.as_str()).map(str::to_string),
+                    _ => None,
+                };
+                deps.push((package.to_string(), version));
+            }
+        };
+        collect(value.get("dependencies"));
+        collect(value.get("dev-dependencies"));
+        collect(value.get("build-dependencies"));
+        collect(value.get("workspace").and_then(|w| w.get("dependencies")));
+        deps
+    }
+
+    fn parse_package_json(content: &str, tech_stack: &mut TechStack) -> Vec<(String, Option<String>)> {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(content) else { return Vec::new() };
+
+        // e.g. "packageManager": "pnpm@8.15.0"
+        if let Some(manager) = value.get("packageManager").and_then(|p| p.as_str()) {
+            let (name, version) = manager.split_once('@').unwrap_or((manager, ""));
+            let display = match name {
+                "npm" => "npm",
+                "yarn" => "Yarn",
+                "pnpm" => "pnpm",
+                "bun" => "Bun",
+                other => other,
+            };
+            tech_stack.add_versioned("build_tool", display, Some(version));
+        }
+
+        let mut deps = Vec::new();
+        for section in ["dependencies", "devDependencies", "peerDependencies"] {
+            if let Some(map) = value.get(section).and_then(|d| d.as_object()) {
+                for (name, version) in map {
+                    deps.push((name.clone(), version.as_str().map(str::to_string)));
+                }
+            }
+        }
+        deps
+    }
+
+    fn parse_requirements(content: &str) -> Vec<(String, Option<String>)> {
+        content.lines()
+            .map(|line| line.split('#').next().unwrap_or("").trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('-'))
+            .map(Self::parse_pep508)
+            .collect()
+    }
+
+    /// Splits a PEP 508 requirement such as `Django[argon2]>=4.2; python_version>"3.8"`
+    /// into name and version specifier.
+    fn parse_pep508(requirement: &str) -> (String, Option<String>) {
+        let requirement = requirement.split(';').next().unwrap_or("").trim();
+        let name_end = requirement
+            .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_' || c == '.'))
+            .unwrap_or(requirement.len());
+        let name = requirement[..name_end].to_string();
+        let rest = requirement[name_end..].trim();
+        let rest = match rest.strip_prefix('[') {
+            Some(extras) => extras.split_once(']').map(|(_, r)| r.trim()).unwrap_or(""),
+            None => rest,
+        };
+        let version = Some(rest.trim_start_matches(['(']).trim_end_matches([')']).trim().to_string())
+            .filter(|v| !v.is_empty());
+        (name, version)
+    }
+
+    fn parse_toml_dependency_table(table: Option<&toml::Value>) -> Vec<(String, Option<String>)> {
+        let Some(table) = table.and_then(|t| t.as_table()) else { return Vec::new() };
+        table.iter()
+            .filter(|(name, _)| name.as_str() != "python")
+            .map(|(name, spec)| {
+                let version = match spec {
+                    toml::Value::String(v) if v != "*" => Some(v.clone()),
+                    toml::Value::Table(t) => t.get("version").and_then(|v| v.as_str()).map(str::to_string),
+                    _ => None,
+                };
+                (name.clone(), version)
+            })
+            .collect()
+    }
+
+    fn parse_pyproject(content: &str, tech_stack: &mut TechStack) -> Vec<(String, Option<String>)> {
+        let Ok(value) = content.parse::<toml::Table>() else { return Vec::new() };
+
+        let backend = value.get("build-system")
+            .and_then(|b| b.get("build-backend"))
+            .and_then(|b| b.as_str())
+            .unwrap_or("");
+        let build_tool = match backend {
+            b if b.starts_with("poetry") => Some("Poetry"),
+            b if b.starts_with("hatchling") => Some("Hatch"),
+            b if b.starts_with("setuptools") => Some("setuptools"),
+            b if b.starts_with("flit") => Some("Flit"),
+            b if b.starts_with("pdm") => Some("PDM"),
+            b if b.starts_with("maturin") => Some("maturin"),
+            _ => None,
+        };
+        if let Some(tool) = build_tool {
+            tech_stack.add("build_tool", tool);
+        }
+        if value.get("tool").and_then(|t| t.get("uv")).is_some() {
+            tech_stack.add("build_tool", "uv");
+        }
+
+        let mut deps: Vec<(String, Option<String>)> = value.get("project")
+            .and_then(|p| p.get("dependencies"))
+            .and_then(|d| d.as_array())
+            .map(|list| list.iter().filter_map(|d| d.as_str()).map(Self::parse_pep508).collect())
+            .unwrap_or_default();
+
+        let poetry = value.get("tool").and_then(|t| t.get("poetry"));
+        deps.extend(Self::parse_toml_dependency_table(poetry.and_then(|p| p.get("dependencies"))));
+        deps.extend(Self::parse_toml_dependency_table(poetry.and_then(|p| p.get("dev-dependencies"))));
+        deps
+    }
+
+    fn parse_pipfile(content: &str) -> Vec<(String, Option<String>)> {
+        let Ok(value) = content.parse::<toml::Table>() else { return Vec::new() };
+        let mut deps = Self::parse_toml_dependency_table(value.get("packages"));
+        deps.extend(Self::parse_toml_dependency_table(value.get("dev-packages")));
+        deps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_go_mod_versions() {
+        let content = r#"
+module example.com/api
+
+go 1.22
+
+require github.com/spf13/cobra v1.8.0
+
+require (
+    github.com/gin-gonic/gin v1.9.1
+    github.com/jackc/pgx/v5 v5.5.0 // indirect
+)
+"#;
+        let mut stack = TechStack::default();
+        ManifestAnalyzer::analyze("go.mod", content, &mut stack);
+
+        assert_eq!(stack.frameworks, vec!["Gin (v1.9.1)"]);
+        assert_eq!(stack.databases, vec!["pgx (v5.5.0)"]);
+        assert_eq!(stack.libraries, vec!["Cobra (v1.8.0)"]);
+        assert_eq!(stack.build_tools, vec!["Go modules"]);
+    }
+
+    #[test]
+    fn test_package_json_and_lockfile() {
+        let content = r#"{
+  "packageManager": "pnpm@8.15.0",
+  "dependencies": { "express": "^4.18.2", "@prisma/client": "5.0.0" },
+  "devDependencies": { "vitest": "^1.2.0" }
+}"#;
+        let mut stack = TechStack::default();
+        stack.add("framework", "Express");
+        ManifestAnalyzer::analyze("package.json", content, &mut stack);
+        ManifestAnalyzer::analyze("pnpm-lock.yaml", "", &mut stack);
+
+        assert_eq!(stack.frameworks, vec!["Express (^4.18.2)"]);
+        assert_eq!(stack.databases, vec!["Prisma (5.0.0)"]);
+        assert_eq!(stack.libraries, vec!["Vitest (^1.2.0)"]);
+        assert_eq!(stack.build_tools, vec!["pnpm (8.15.0)"]);
+    }
+
+    #[test]
+    fn test_python_manifests() {
+        let pyproject = r#"
+[project]
+dependencies = ["fastapi>=0.110", "SQLAlchemy[asyncio]==2.0.29; python_version>'3.8'"]
+
+[build-system]
+build-backend = "hatchling.build"
+
+[tool.uv]
+dev-dependencies = []
+"#;
+        let mut stack = TechStack::default();
+        ManifestAnalyzer::analyze("pyproject.toml", pyproject, &mut stack);
+        ManifestAnalyzer::analyze("requirements-dev.txt", "# tools\npytest==8.1.1\n-r requirements.txt\n", &mut stack);
+
+        assert_eq!(stack.frameworks, vec!["FastAPI (>=0.110)"]);
+        assert_eq!(stack.databases, vec!["SQLAlchemy (==2.0.29)"]);
+        assert_eq!(stack.libraries, vec!["pytest (==8.1.1)"]);
+        assert_eq!(stack.build_tools, vec!["Hatch", "uv"]);
+    }
+
+    #[test]
+    fn test_cargo_toml_renamed_and_workspace_dependencies() {
+        let content = r#"
+[workspace.dependencies]
+tokio = { version = "1.37", features = ["full"] }
+
+[dependencies]
+web = { package = "axum", version = "0.7" }
+serde = "1.0"
+"#;
+        let mut stack = TechStack::default();
+        ManifestAnalyzer::analyze("Cargo.toml", content, &mut stack);
+
+        assert_eq!(stack.frameworks, vec!["Axum (0.7)"]);
+        assert!(stack.libraries.iter().any(|entry| *entry == "Tokio (1.37)"));
+        assert!(stack.libraries.iter().any(|entry| *entry == "Serde (1.0)"));
+        assert_eq!(stack.build_tools, vec!["Cargo"]);
+    }
+
+    #[test]
+    fn test_display_names_with_parentheses_across_manifests() {
+        let mut stack = TechStack::default();
+        ManifestAnalyzer::analyze("go.mod", "module example.com/api\n\nrequire github.com/lib/pq v1.10.9\n", &mut stack);
+        ManifestAnalyzer::analyze("requirements.txt", "psycopg2==2.9.9\n", &mut stack);
+        ManifestAnalyzer::analyze("go.mod", "module example.com/worker\n\nrequire github.com/lib/pq v1.10.9\n", &mut stack);
+        ManifestAnalyzer::analyze("requirements.txt", "psycopg2-binary==2.9.9\n", &mut stack);
+
+        assert_eq!(stack.databases, vec!["PostgreSQL (lib/pq) (v1.10.9)", "PostgreSQL (psycopg2) (==2.9.9)"]);
+    }
+}
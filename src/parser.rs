@@ -1,15 +1,29 @@
 use std::path::Path;
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
 use tree_sitter::{Language, Parser};
+use crate::plugins::{self, PluginRegistry};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum SupportedLanguage {
     Go,
     Python,
     TypeScript,
     JavaScript,
     Rust,
+    Java,
+    /// A language registered at runtime, see `crate::plugins`.
+    Plugin(&'static str),
 }
 
+const BUILT_IN: [SupportedLanguage; 6] = [
+    SupportedLanguage::Go,
+    SupportedLanguage::Python,
+    SupportedLanguage::TypeScript,
+    SupportedLanguage::JavaScript,
+    SupportedLanguage::Rust,
+    SupportedLanguage::Java,
+];
+
 impl SupportedLanguage {
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -18,8 +32,52 @@ impl SupportedLanguage {
             SupportedLanguage::TypeScript => "TypeScript",
             SupportedLanguage::JavaScript => "JavaScript",
             SupportedLanguage::Rust => "Rust",
+            SupportedLanguage::Java => "Java",
+            SupportedLanguage::Plugin(name) => name,
         }
     }
+
+    /// Case-insensitive inverse of `as_str`, for languages named in config
+    /// files. Plugin languages are found through `PluginRegistry::language`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        BUILT_IN.into_iter().find(|language| language.as_str().eq_ignore_ascii_case(name))
+    }
+
+    /// Every grammar `CodeParser` may parse a file of this language with,
+    /// the main one first. Plugin grammars come from `PluginRegistry::grammars`.
+    pub fn grammars(&self) -> Vec<Language> {
+        match self {
+            SupportedLanguage::Go => vec![tree_sitter_go::LANGUAGE.into()],
+            SupportedLanguage::Python => vec![tree_sitter_python::LANGUAGE.into()],
+            SupportedLanguage::TypeScript => vec![
+                tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+                tree_sitter_typescript::LANGUAGE_TSX.into(),
+            ],
+            SupportedLanguage::JavaScript => vec![
+                tree_sitter_javascript::LANGUAGE.into(),
+                tree_sitter_typescript::LANGUAGE_TSX.into(),
+            ],
+            SupportedLanguage::Rust => vec![tree_sitter_rust::LANGUAGE.into()],
+            SupportedLanguage::Java => vec![tree_sitter_java::LANGUAGE.into()],
+            SupportedLanguage::Plugin(_) => Vec::new(),
+        }
+    }
+}
+
+/// Languages are stored by name, e.g. as keys of `AnalysisResult::languages`.
+impl Serialize for SupportedLanguage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for SupportedLanguage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(BUILT_IN.into_iter()
+            .find(|language| language.as_str() == name)
+            .unwrap_or_else(|| SupportedLanguage::Plugin(plugins::intern(&name))))
+    }
 }
 
 pub struct CodeParser {
@@ -28,13 +86,34 @@ pub struct CodeParser {
 }
 
 impl CodeParser {
+    /// A parser for one of the built-in languages.
     pub fn new(path: &Path) -> Option<Self> {
+        Self::with_plugins(path, &PluginRegistry::default())
+    }
+
+    /// Like `new`, also parsing the extensions of `plugins`.
+    pub fn with_plugins(path: &Path, plugins: &PluginRegistry) -> Option<Self> {
         let extension = path.extension()?.to_str()?;
+        Self::builtin(extension).or_else(|| {
+            let plugin = plugins.find_by_extension(extension)?;
+            Some(Self {
+                language: SupportedLanguage::Plugin(plugin.name),
+                ts_language: plugin.grammar.clone(),
+            })
+        })
+    }
+
+    /// The built-in language files with `extension` are parsed as, if any.
+    pub(crate) fn builtin(extension: &str) -> Option<Self> {
         let (language, ts_language) = match extension {
             "go" => (SupportedLanguage::Go, tree_sitter_go::LANGUAGE.into()),
             "py" => (SupportedLanguage::Python, tree_sitter_python::LANGUAGE.into()),
-            "ts" | "tsx" | "js" | "jsx" => (SupportedLanguage::TypeScript, tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+            "ts" | "mts" | "cts" => (SupportedLanguage::TypeScript, tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+            "tsx" => (SupportedLanguage::TypeScript, tree_sitter_typescript::LANGUAGE_TSX.into()),
+            "js" | "mjs" | "cjs" => (SupportedLanguage::JavaScript, tree_sitter_javascript::LANGUAGE.into()),
+            "jsx" => (SupportedLanguage::JavaScript, tree_sitter_typescript::LANGUAGE_TSX.into()),
             "rs" => (SupportedLanguage::Rust, tree_sitter_rust::LANGUAGE.into()),
+            "java" => (SupportedLanguage::Java, tree_sitter_java::LANGUAGE.into()),
             _ => return None,
         };
 
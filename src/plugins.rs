@@ -0,0 +1,425 @@
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use anyhow::Context;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use streaming_iterator::StreamingIterator;
+use tree_sitter::{Language, Query, QueryCursor};
+use crate::languages::LanguageAnalyzer;
+use crate::parser::{CodeParser, SupportedLanguage};
+use crate::rules::{merge_first, AnalysisResult};
+use crate::rules::naming::IdentifierKind;
+
+/// Default location of language plugins, relative to the analyzed root.
+pub const LANGUAGES_DIR: &str = ".buddy/languages";
+
+/// Names of every plugin language seen, including ones only read back from
+/// a cache, so `SupportedLanguage::Plugin` can stay `Copy`. Only the names are
+/// process-wide; the plugins themselves belong to a `PluginRegistry`.
+static NAMES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+/// `<language>.toml` in the plugins directory.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PluginManifest {
+    name: String,
+    extensions: Vec<String>,
+    grammar: GrammarSource,
+    #[serde(default)]
+    queries: Vec<QueryManifest>,
+}
+
+/// Either one of the grammars compiled into buddy or a shared library built
+/// with `tree-sitter build`, e.g. `libtree-sitter-ruby.so`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GrammarSource {
+    builtin: Option<String>,
+    /// Path of the shared library, relative to the manifest.
+    library: Option<String>,
+    /// Exported language function. Defaults to `tree_sitter_<name>`.
+    symbol: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct QueryManifest {
+    target: QueryTarget,
+    /// Inline query source.
+    query: Option<String>,
+    /// Query file, relative to the manifest.
+    file: Option<String>,
+    /// Recorded for each match. Without it, each captured node's text is.
+    value: Option<String>,
+}
+
+/// The `AnalysisResult` field a plugin query feeds.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum QueryTarget {
+    #[serde(rename = "naming.variable")]
+    Variable,
+    #[serde(rename = "naming.function")]
+    Function,
+    #[serde(rename = "naming.type")]
+    Type,
+    #[serde(rename = "tech_stack.frameworks")]
+    Frameworks,
+    #[serde(rename = "tech_stack.libraries")]
+    Libraries,
+    #[serde(rename = "tech_stack.databases")]
+    Databases,
+    #[serde(rename = "tech_stack.build_tools")]
+    BuildTools,
+    #[serde(rename = "design_patterns.patterns")]
+    DesignPatterns,
+    #[serde(rename = "error_handling.failure_patterns")]
+    FailurePatterns,
+    #[serde(rename = "di.injection_patterns")]
+    InjectionPatterns,
+    #[serde(rename = "config.config_sources")]
+    ConfigSources,
+    #[serde(rename = "testing.mocking_strategy")]
+    MockingStrategy,
+    #[serde(rename = "testing.assertion_style")]
+    AssertionStyle,
+    #[serde(rename = "security.input_sanitization")]
+    InputSanitization,
+}
+
+struct PluginQuery {
+    target: QueryTarget,
+    value: Option<String>,
+    query: Query,
+}
+
+/// A language described by data instead of a `LanguageAnalyzer` module.
+pub struct LanguagePlugin {
+    pub name: &'static str,
+    pub extensions: Vec<String>,
+    pub grammar: Language,
+    queries: Vec<PluginQuery>,
+    fingerprint: String,
+    /// Keeps a grammar loaded from a shared library mapped while `grammar` is in use.
+    _library: Option<libloading::Library>,
+}
+
+impl LanguagePlugin {
+    /// Reads a manifest and compiles its queries. A `grammar.library` is only
+    /// loaded with `allow_libraries`, since loading runs the library's code.
+    pub fn load(manifest_path: &Path, allow_libraries: bool) -> anyhow::Result<Self> {
+        let source = std::fs::read_to_string(manifest_path)
+            .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+        let manifest: PluginManifest = toml::from_str(&source)
+            .with_context(|| format!("Invalid language plugin {}", manifest_path.display()))?;
+        let base = manifest_path.parent().unwrap_or(Path::new("."));
+        let mut hasher = Sha256::new();
+        hasher.update(&source);
+
+        let (grammar, library) = match (&manifest.grammar.builtin, &manifest.grammar.library) {
+            (Some(builtin), None) => (Self::builtin_grammar(builtin)?, None),
+            (None, Some(_)) if !allow_libraries => anyhow::bail!(
+                "{}: shared-library grammars are only loaded with --allow-plugin-libraries", manifest_path.display()),
+            (None, Some(library)) => {
+                let symbol = manifest.grammar.symbol.clone()
+                    .unwrap_or_else(|| format!("tree_sitter_{}", manifest.name.to_lowercase().replace('-', "_")));
+                let (grammar, library) = Self::load_grammar(&base.join(library), &symbol)?;
+                (grammar, Some(library))
+            }
+            _ => anyhow::bail!("{}: set exactly one of grammar.builtin and grammar.library", manifest_path.display()),
+        };
+
+        let mut queries = Vec::new();
+        for entry in manifest.queries {
+            let text = match (entry.query, entry.file) {
+                (Some(query), None) => query,
+                (None, Some(file)) => std::fs::read_to_string(base.join(&file))
+                    .with_context(|| format!("Failed to read {}", base.join(&file).display()))?,
+                _ => anyhow::bail!("{}: each query needs exactly one of `query` and `file`", manifest_path.display()),
+            };
+            hasher.update(&text);
+            let query = Query::new(&grammar, &text)
+                .with_context(|| format!("Invalid {:?} query in {}", entry.target, manifest_path.display()))?;
+            queries.push(PluginQuery { target: entry.target, value: entry.value, query });
+        }
+
+        Ok(Self {
+            name: intern(&manifest.name),
+            extensions: manifest.extensions.iter().map(|ext| ext.trim_start_matches('.').to_string()).collect(),
+            grammar,
+            queries,
+            fingerprint: hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect(),
+            _library: library,
+        })
+    }
+
+    fn builtin_grammar(name: &str) -> anyhow::Result<Language> {
+        Ok(match name.to_lowercase().as_str() {
+            "go" => tree_sitter_go::LANGUAGE.into(),
+            "python" => tree_sitter_python::LANGUAGE.into(),
+            "typescript" => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            "tsx" => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            "javascript" => tree_sitter_javascript::LANGUAGE.into(),
+            "rust" => tree_sitter_rust::LANGUAGE.into(),
+            "java" => tree_sitter_java::LANGUAGE.into(),
+            _ => anyhow::bail!("Unknown built-in grammar `{}`", name),
+        })
+    }
+
+    fn load_grammar(path: &Path, symbol: &str) -> anyhow::Result<(Language, libloading::Library)> {
+        // SAFETY: loading runs the library's initializers. Grammars are plain
+        // generated C, and the symbol is the language function every
+        // tree-sitter grammar exports with this signature.
+        unsafe {
+            let library = libloading::Library::new(path)
+                .with_context(|| format!("Failed to load grammar {}", path.display()))?;
+            let language_fn = *library
+                .get::<unsafe extern "C" fn() -> *const ()>(symbol.as_bytes())
+                .with_context(|| format!("Symbol `{}` not found in {}", symbol, path.display()))?;
+            let grammar = Language::new(tree_sitter_language::LanguageFn::from_raw(language_fn));
+            let version = grammar.version();
+            if !(tree_sitter::MIN_COMPATIBLE_LANGUAGE_VERSION..=tree_sitter::LANGUAGE_VERSION).contains(&version) {
+                anyhow::bail!("Grammar {} has ABI version {}, buddy supports {} to {}", path.display(), version,
+                    tree_sitter::MIN_COMPATIBLE_LANGUAGE_VERSION, tree_sitter::LANGUAGE_VERSION);
+            }
+            Ok((grammar, library))
+        }
+    }
+}
+
+/// The language plugins one analysis uses, see `ProjectAnalyzer::with_plugins`.
+/// Cloning is cheap and shares the loaded plugins.
+#[derive(Clone, Default)]
+pub struct PluginRegistry {
+    plugins: Vec<Arc<LanguagePlugin>>,
+    allow_libraries: bool,
+}
+
+impl PluginRegistry {
+    /// Lets `load_dir` load `grammar.library` manifests. Off by default: the
+    /// plugins directory belongs to the analyzed repository, which may not be
+    /// trusted to run native code.
+    pub fn with_libraries(mut self, allowed: bool) -> Self {
+        self.allow_libraries = allowed;
+        self
+    }
+
+    /// Adds `plugin` to this registry. Built-in languages and their extensions
+    /// can't be replaced.
+    pub fn register(&mut self, plugin: LanguagePlugin) -> anyhow::Result<SupportedLanguage> {
+        if self.language(plugin.name).is_some() {
+            anyhow::bail!("Language `{}` is already registered", plugin.name);
+        }
+        if let Some(ext) = plugin.extensions.iter().find(|ext| CodeParser::builtin(ext).is_some()) {
+            anyhow::bail!("Extension `.{}` of `{}` belongs to a built-in language", ext, plugin.name);
+        }
+        if let Some(ext) = plugin.extensions.iter().find(|ext| self.find_by_extension(ext).is_some()) {
+            anyhow::bail!("Extension `.{}` of `{}` is already registered", ext, plugin.name);
+        }
+        let language = SupportedLanguage::Plugin(plugin.name);
+        self.plugins.push(Arc::new(plugin));
+        Ok(language)
+    }
+
+    /// Registers every `*.toml` manifest in `dir`. A missing directory means no plugins.
+    pub fn load_dir(&mut self, dir: &Path) -> anyhow::Result<Vec<SupportedLanguage>> {
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .with_context(|| format!("Failed to read {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+        paths.iter().map(|path| self.register(LanguagePlugin::load(path, self.allow_libraries)?)).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plugins.is_empty()
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Arc<LanguagePlugin>> {
+        self.plugins.iter().find(|plugin| plugin.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn find_by_extension(&self, extension: &str) -> Option<&Arc<LanguagePlugin>> {
+        self.plugins.iter().find(|plugin| plugin.extensions.iter().any(|ext| ext == extension))
+    }
+
+    /// `SupportedLanguage::from_name`, also finding the registered plugins.
+    pub fn language(&self, name: &str) -> Option<SupportedLanguage> {
+        SupportedLanguage::from_name(name)
+            .or_else(|| self.find(name).map(|plugin| SupportedLanguage::Plugin(plugin.name)))
+    }
+
+    /// `SupportedLanguage::grammars`, also for the registered plugins.
+    pub fn grammars(&self, language: SupportedLanguage) -> Vec<Language> {
+        match language {
+            SupportedLanguage::Plugin(name) => self.find(name).map(|plugin| plugin.grammar.clone()).into_iter().collect(),
+            _ => language.grammars(),
+        }
+    }
+
+    /// Identifies the registered plugins, for the analysis cache.
+    pub fn fingerprint(&self) -> String {
+        self.plugins.iter().map(|plugin| plugin.fingerprint.as_str()).collect()
+    }
+}
+
+pub(crate) fn intern(name: &str) -> &'static str {
+    let mut names = NAMES.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(existing) = names.iter().find(|existing| **existing == name) {
+        return existing;
+    }
+    let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+    names.push(name);
+    name
+}
+
+/// Runs a plugin's query bundle. Languages missing from the registry, e.g.
+/// ones only read back from a cache, record nothing.
+pub struct PluginAnalyzer {
+    plugin: Option<Arc<LanguagePlugin>>,
+}
+
+impl PluginAnalyzer {
+    pub fn new(name: &str, plugins: &PluginRegistry) -> Self {
+        Self { plugin: plugins.find(name).cloned() }
+    }
+}
+
+impl LanguageAnalyzer for PluginAnalyzer {
+    fn analyze(&self, content: &str, tree: &tree_sitter::Tree, result: &mut AnalysisResult) {
+        let Some(plugin) = &self.plugin else { return };
+        let source = content.as_bytes();
+
+        for entry in &plugin.queries {
+            let mut cursor = QueryCursor::new();
+            let mut matches = cursor.matches(&entry.query, tree.root_node(), source);
+            while let Some(m) = matches.next() {
+                match (&entry.value, entry.target) {
+                    (_, QueryTarget::Variable | QueryTarget::Function | QueryTarget::Type) | (None, _) => {
+                        for capture in m.captures {
+                            let text = capture.node.utf8_text(source).unwrap_or("");
+                            Self::record(result, entry.target, text, capture.node.start_position().row + 1);
+                        }
+                    }
+                    (Some(value), target) => Self::record(result, target, value, 0),
+                }
+            }
+        }
+    }
+}
+
+impl PluginAnalyzer {
+    fn record(result: &mut AnalysisResult, target: QueryTarget, value: &str, line: usize) {
+        if value.is_empty() {
+            return;
+        }
+        let push = |list: &mut Vec<String>| {
+            if !list.iter().any(|item| item == value) {
+                list.push(value.to_string());
+            }
+        };
+        match target {
+            QueryTarget::Variable => result.naming.record_identifier(IdentifierKind::Variable, value, line),
+            QueryTarget::Function => result.naming.record_identifier(IdentifierKind::Function, value, line),
+            QueryTarget::Type => result.naming.record_identifier(IdentifierKind::Type, value, line),
+            QueryTarget::Frameworks => result.tech_stack.add("framework", value),
+            QueryTarget::Libraries => result.tech_stack.add("library", value),
+            QueryTarget::Databases => result.tech_stack.add("database", value),
+            QueryTarget::BuildTools => result.tech_stack.add("build_tool", value),
+            QueryTarget::DesignPatterns => push(&mut result.design_patterns.patterns),
+            QueryTarget::FailurePatterns => push(&mut result.error_handling.failure_patterns),
+            QueryTarget::InjectionPatterns => push(&mut result.di.injection_patterns),
+            QueryTarget::ConfigSources => push(&mut result.config.config_sources),
+            QueryTarget::MockingStrategy => merge_first(&mut result.testing.mocking_strategy, value.to_string()),
+            QueryTarget::AssertionStyle => merge_first(&mut result.testing.assertion_style, value.to_string()),
+            QueryTarget::InputSanitization => merge_first(&mut result.security.input_sanitization, value.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::ProjectAnalyzer;
+    use crate::rules::naming::Casing;
+
+    #[test]
+    fn test_plugin_language_with_builtin_grammar() {
+        let dir = tempfile::tempdir().unwrap();
+        let plugins = dir.path().join("languages");
+        std::fs::create_dir_all(&plugins).unwrap();
+        std::fs::write(plugins.join("functions.scm"), "(function_definition name: (identifier) @name)").unwrap();
+        std::fs::write(plugins.join("starlark.toml"), r#"
+            name = "Starlark"
+            extensions = ["bzl", ".star"]
+            grammar = { builtin = "python" }
+
+            [[queries]]
+            target = "naming.function"
+            file = "functions.scm"
+
+            [[queries]]
+            target = "tech_stack.build_tools"
+            query = '((call function: (identifier) @fn) (#eq? @fn "cc_binary"))'
+            value = "Bazel"
+        "#).unwrap();
+
+        let mut registry = PluginRegistry::default();
+        let registered = registry.load_dir(&plugins).unwrap();
+        let starlark = SupportedLanguage::Plugin("Starlark");
+        assert_eq!(registered, vec![starlark]);
+        assert_eq!(CodeParser::with_plugins(Path::new("defs.star"), &registry).unwrap().language, starlark);
+        assert_eq!(registry.language("starlark"), Some(starlark));
+        assert!(registry.clone().load_dir(&plugins).is_err(), "registering twice must fail");
+
+        // Other registries, and so other analyzers, don't see the plugin.
+        assert!(CodeParser::new(Path::new("defs.star")).is_none());
+        assert_eq!(PluginRegistry::default().load_dir(&plugins).unwrap(), vec![starlark]);
+
+        let repo = dir.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        std::fs::write(repo.join("rules.bzl"), "def cc_library_set():\n    pass\n\ndef make_binary():\n    cc_binary(name = \"app\")\n").unwrap();
+        assert!(ProjectAnalyzer::new(&repo).analyze().unwrap().language_counts.is_empty());
+        let result = ProjectAnalyzer::new(&repo).with_plugins(registry).analyze().unwrap();
+
+        assert_eq!(result.language_counts["Starlark"], 1);
+        assert_eq!(result.languages[&starlark].naming.function_casing, Casing::SnakeCase);
+        assert_eq!(result.tech_stack.build_tools, vec!["Bazel"]);
+
+        let json = serde_json::to_string(&result.languages).unwrap();
+        assert!(json.starts_with("{\"Starlark\":"));
+    }
+
+    #[test]
+    fn test_invalid_manifests_are_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("lang.toml");
+        std::fs::write(&manifest, "name = \"Go\"\nextensions = [\"go2\"]\ngrammar = { builtin = \"go\" }\n").unwrap();
+        assert!(PluginRegistry::default().register(LanguagePlugin::load(&manifest, false).unwrap()).unwrap_err().to_string().contains("already registered"));
+
+        // Built-in languages would match `.py` first and silently shadow the plugin.
+        std::fs::write(&manifest, "name = \"Starlark\"\nextensions = [\"star\", \"py\"]\ngrammar = { builtin = \"python\" }\n").unwrap();
+        let err = PluginRegistry::default().register(LanguagePlugin::load(&manifest, false).unwrap()).unwrap_err();
+        assert!(err.to_string().contains("`.py` of `Starlark` belongs to a built-in language"), "{}", err);
+
+        std::fs::write(&manifest, "name = \"Broken\"\nextensions = [\"brk\"]\ngrammar = { builtin = \"go\" }\n[[queries]]\ntarget = \"naming.function\"\nquery = \"(no_such_node) @n\"\n").unwrap();
+        assert!(LanguagePlugin::load(&manifest, false).is_err());
+
+        std::fs::write(&manifest, "name = \"Missing\"\nextensions = [\"mis\"]\ngrammar = { library = \"libtree-sitter-missing.so\" }\n").unwrap();
+        assert!(format!("{:#}", LanguagePlugin::load(&manifest, true).err().unwrap()).contains("Failed to load grammar"));
+    }
+
+    #[test]
+    fn test_libraries_are_opt_in() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("ruby.toml"), "name = \"Ruby\"\nextensions = [\"rb\"]\ngrammar = { library = \"libtree-sitter-ruby.so\" }\n").unwrap();
+
+        let error = PluginRegistry::default().load_dir(dir.path()).unwrap_err();
+        assert!(error.to_string().contains("--allow-plugin-libraries"));
+        let error = PluginRegistry::default().with_libraries(true).load_dir(dir.path()).unwrap_err();
+        assert!(format!("{:#}", error).contains("Failed to load grammar"));
+    }
+}
@@ -1,129 +1,342 @@
-use crate::rules::AnalysisResult;
+use std::collections::BTreeMap;
+use serde::{Serialize, Deserialize};
+use crate::i18n::{self, Locale};
+use crate::rules::{AnalysisResult, LanguageFindings};
+use crate::rules::naming::{Casing, CasingHistogram, NamingViolations};
+use crate::rules::error_handling::ErrorHandlingAnalysis;
+use crate::rules::commands::CommandKind;
+use crate::rules::diff::DiffAnalysis;
+use crate::rules::custom::{CustomRuleAnalysis, CustomRuleFinding};
+
+/// Minimum number of files a language needs before it gets its own subsection.
+const MIN_LANGUAGE_SECTION_FILES: usize = 3;
+
+/// Clones beyond this are summarized as a count; the JSON output has them all.
+const MAX_LISTED_CLONES: usize = 20;
+
+/// Custom rule findings listed per report group before the rest are counted.
+const MAX_LISTED_RULE_FINDINGS: usize = 20;
+
+/// Below this share of `fmt.Errorf` calls wrapping their error with `%w`, the
+/// report recommends wrapping.
+const MIN_WRAPPED_ERRORS_PERCENT: usize = 50;
+
+/// Files listed with their `unwrap()`/`expect()` counts, and `unsafe` blocks
+/// listed, before the rest are counted.
+const MAX_LISTED_AUDIT_ITEMS: usize = 10;
+
+/// A `##` section of the markdown report, as named in `.buddy.toml`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReportSection {
+    /// "Changes Since", only present with `--since`.
+    Changes,
+    /// Build, test, lint and other project commands.
+    Commands,
+    CodeStyle,
+    NamingViolations,
+    /// Findings of the project's own rules, only present when they match.
+    CustomRules,
+    Workflow,
+    TechStack,
+    DependencyInjection,
+    Testing,
+    Configuration,
+    Security,
+    ErrorHandling,
+    DesignPatterns,
+    Dry,
+    /// LLM insights, only present with `--with-llm`.
+    Llm,
+}
+
+impl ReportSection {
+    pub const ALL: &'static [ReportSection] = &[
+        ReportSection::Changes,
+        ReportSection::Commands,
+        ReportSection::CodeStyle,
+        ReportSection::NamingViolations,
+        ReportSection::CustomRules,
+        ReportSection::Workflow,
+        ReportSection::TechStack,
+        ReportSection::DependencyInjection,
+        ReportSection::Testing,
+        ReportSection::Configuration,
+        ReportSection::Security,
+        ReportSection::ErrorHandling,
+        ReportSection::DesignPatterns,
+        ReportSection::Dry,
+        ReportSection::Llm,
+    ];
+}
 
 pub struct ReportGenerator;
 
 impl ReportGenerator {
-    pub fn generate(result: &AnalysisResult) -> String {
+    pub fn generate(result: &AnalysisResult, locale: Locale) -> String {
+        Self::generate_sections(result, locale, ReportSection::ALL)
+    }
+
+    /// Like `generate`, but only with the given sections. Sections always
+    /// appear in report order, whatever the order of `enabled`.
+    pub fn generate_sections(result: &AnalysisResult, locale: Locale, enabled: &[ReportSection]) -> String {
+        let emit = |section: ReportSection| enabled.contains(&section);
         let mut report = String::new();
         let dominant_lang = Self::get_dominant_language(result);
+        let sections = Self::language_sections(result, &dominant_lang);
 
         report.push_str("# CLAUDE.md\n\n");
         report.push_str(&format!("> **Dominant Language**: {}\n\n", dominant_lang));
 
-        // 1. Build Commands
-        report.push_str("## Build Commands\n");
-        report.push_str(&Self::get_build_commands(&dominant_lang, result));
-        report.push('\n');
-
-        // 2. Test Commands
-        report.push_str("## Test Commands\n");
-        report.push_str(&Self::get_test_commands(&dominant_lang, result));
-        report.push('\n');
-
-        // 3. Code Style
-        report.push_str("## Code Style\n");
-        report.push_str(&format!("- **Variable Casing**: {}\n", result.naming.variable_casing));
-        report.push_str(&format!("- **Function Casing**: {}\n", result.naming.function_casing));
-        report.push_str(&format!("- **Class/Struct Naming**: {}\n", result.naming.class_struct_naming));
-        report.push_str(&format!("- **File Naming**: {}\n", result.naming.file_naming));
-        if let Some(prefix) = &result.naming.interface_prefix {
-            report.push_str(&format!("- **Interface Prefix**: `{}`\n", prefix));
-        }
-        report.push_str(&format!("- **Comment Style**: {}\n", Self::format_val(&result.naming.comment_style)));
-        let naming_pattern = format!("{} {} {}", result.naming.variable_casing, result.naming.function_casing, result.naming.class_struct_naming);
-        Self::append_context(&mut report, &dominant_lang, "naming", &naming_pattern);
-        report.push('\n');
-
-        // 4. Workflow
-        report.push_str("## Workflow\n");
-        report.push_str("- **Always** run tests before committing changes.\n");
-        report.push_str("- Use descriptive commit messages following Conventional Commits if possible.\n");
-        report.push_str("- Follow the existing architecture patterns described below.\n");
-        report.push('\n');
+        if emit(ReportSection::Changes) {
+            if let Some(diff) = &result.diff {
+                report.push_str(&Self::format_diff(diff, &result.naming_violations));
+            }
+        }
+
+        if emit(ReportSection::Commands) {
+            // 1. Build Commands
+            report.push_str("## Build Commands\n");
+            report.push_str(&Self::get_build_commands(&dominant_lang, result));
+            report.push('\n');
+
+            // 2. Test Commands
+            report.push_str("## Test Commands\n");
+            report.push_str(&Self::get_test_commands(&dominant_lang, result));
+            report.push('\n');
+
+            for (kind, heading) in [(CommandKind::Lint, "## Lint & Format Commands\n"), (CommandKind::Other, "## Other Project Commands\n")] {
+                let commands = Self::format_project_commands(result, kind);
+                if !commands.is_empty() {
+                    report.push_str(heading);
+                    report.push_str(&commands);
+                    report.push('\n');
+                }
+            }
+        }
+
+        if emit(ReportSection::CodeStyle) {
+            // 3. Code Style
+            report.push_str("## Code Style\n");
+            for (lang, findings) in &sections {
+                Self::push_language_heading(&mut report, lang, findings, sections.len());
+                let naming = &findings.naming;
+                report.push_str(&format!("- **Variable Casing**: {}\n", Self::format_casing(&naming.variable_histogram)));
+                report.push_str(&format!("- **Function Casing**: {}\n", Self::format_casing(&naming.function_histogram)));
+                report.push_str(&format!("- **Class/Struct Naming**: {}\n", Self::format_casing(&naming.class_struct_histogram)));
+                report.push_str(&format!("- **File Naming**: {}\n", Self::format_casing(&naming.file_histogram)));
+                if let Some(prefix) = &naming.interface_prefix {
+                    report.push_str(&format!("- **Interface Prefix**: `{}`\n", prefix));
+                }
+                report.push_str(&format!("- **Comment Style**: {}\n", Self::format_val(&naming.comment_style)));
+                let naming_pattern = format!("{} {} {}", naming.variable_casing, naming.function_casing, naming.class_struct_naming);
+                Self::append_context(&mut report, locale, lang, "naming", &naming_pattern);
+                report.push('\n');
+            }
+        }
+
+        if emit(ReportSection::NamingViolations) && !result.naming_violations.is_empty() {
+            report.push_str("## Naming Violations\n");
+            report.push_str(&Self::format_violations(&result.naming_violations));
+            report.push('\n');
+        }
+
+        if emit(ReportSection::CustomRules) && !result.custom_rules.findings.is_empty() {
+            report.push_str("## Custom Rules\n");
+            report.push_str(&Self::format_custom_rules(&result.custom_rules));
+        }
+
+        if emit(ReportSection::Workflow) {
+            // 4. Workflow
+            report.push_str("## Workflow\n");
+            report.push_str("- **Always** run tests before committing changes.\n");
+            report.push_str("- Use descriptive commit messages following Conventional Commits if possible.\n");
+            report.push_str("- Follow the existing architecture patterns described below.\n");
+            report.push('\n');
+        }
 
         report.push_str("--- \n\n");
         report.push_str("# Advanced Repository Analysis\n\n");
 
-        // 0. Tech Stack & Architecture
-        report.push_str("## 0. Tech Stack & Architecture\n");
-        report.push_str(&format!("- **Architecture Pattern**: {}\n", Self::format_val(&result.architecture.pattern)));
-        if !result.architecture.layers.is_empty() {
-            report.push_str(&format!("- **Architecture Layers**: {}\n", Self::format_bullet_list(&result.architecture.layers)));
-        }
-        report.push_str(&format!("- **Frameworks**: {}\n", Self::format_bullet_list(&result.tech_stack.frameworks)));
-        report.push_str(&format!("- **Databases**: {}\n", Self::format_bullet_list(&result.tech_stack.databases)));
-        report.push_str(&format!("- **Libraries**: {}\n", Self::format_bullet_list(&result.tech_stack.libraries)));
-        report.push('\n');
-
-        // 2. Dependency Injection (DI) & Coupling
-        report.push_str("## 2. Dependency Injection (DI) & Coupling\n");
-        let di_pattern = Self::format_bullet_list(&result.di.injection_patterns);
-        report.push_str(&format!("- **Injection Pattern**: {}\n", di_pattern));
-        report.push_str(&format!("- **Abstraction Level**: {:.2}\n", result.di.abstraction_level));
-        report.push_str(&format!("- **Global State Dependency**: {}\n", Self::format_bullet_list(&result.di.global_state_usage)));
-        Self::append_context(&mut report, &dominant_lang, "di", &di_pattern);
-        report.push('\n');
-
-        // 3. Testing Culture & Style
-        report.push_str("## 3. Testing Culture & Style\n");
-        report.push_str(&format!("- **Test Location**: {}\n", Self::format_val(&result.testing.test_location)));
-        report.push_str(&format!("- **Mocking Strategy**: {}\n", Self::format_val(&result.testing.mocking_strategy)));
-        report.push_str(&format!("- **Naming Pattern**: {}\n", Self::format_val(&result.testing.naming_pattern)));
-        report.push_str(&format!("- **Assertion Style**: {}\n", Self::format_val(&result.testing.assertion_style)));
-        Self::append_context(&mut report, &dominant_lang, "testing", &result.testing.test_location);
-        report.push('\n');
-
-        // 4. Configuration & Environment Management
-        report.push_str("## 4. Configuration & Environment Management\n");
-        report.push_str(&format!("- **Config Source**: {}\n", Self::format_bullet_list(&result.config.config_sources)));
-        report.push_str(&format!("- **Type Safety**: {}\n", Self::format_val(&result.config.type_safety)));
-        report.push_str(&format!("- **Secret Handling**: {}\n", Self::format_val(&result.config.secret_handling)));
-        Self::append_context(&mut report, &dominant_lang, "config", &result.config.type_safety);
-        report.push('\n');
-
-        // 5. Security & Safety Baseline
-        report.push_str("## 5. Security & Safety Baseline\n");
-        let secrets_found = if result.security.hardcoded_secrets.is_empty() { "None detected" } else { "Potential secrets found" };
-        report.push_str(&format!("- **Hardcoded Secrets**: {}\n", secrets_found));
-        report.push_str(&format!("- **Input Sanitization**: {}\n", Self::format_val(&result.security.input_sanitization)));
-        report.push_str(&format!("- **Memory Safety**: {}\n", Self::format_val(&result.security.memory_safety)));
-        report.push_str(&format!("- **Concurrency Safety**: {}\n", Self::format_val(&result.security.concurrency_safety)));
-        Self::append_context(&mut report, &dominant_lang, "security", secrets_found);
-        report.push('\n');
-
-        // 6. Error Handling Strategy
-        report.push_str("## 6. Error Handling Strategy\n");
-        let error_pattern = Self::format_bullet_list(&result.error_handling.failure_patterns);
-        report.push_str(&format!("- **Failure Pattern**: {}\n", error_pattern));
-        report.push_str(&format!("- **Logging Consistency**: {}\n", Self::format_val(&result.error_handling.logging_consistency)));
-        Self::append_context(&mut report, &dominant_lang, "error_handling", &error_pattern);
-        report.push('\n');
-
-        // 7. Design Patterns
-        report.push_str("## 7. Design Patterns\n");
-        let patterns = Self::format_bullet_list(&result.design_patterns.patterns);
-        report.push_str(&format!("- **Detected Patterns**: {}\n", patterns));
-        Self::append_context(&mut report, &dominant_lang, "design_patterns", &patterns);
-        report.push('\n');
-
-        // 8. DRY Analysis
-        report.push_str("## 8. DRY Analysis\n");
-        let duplication = if result.dry.duplicated_blocks.is_empty() {
-            "No significant duplication detected".to_string()
-        } else {
-            Self::format_bullet_list(&result.dry.duplicated_blocks)
-        };
-        report.push_str(&format!("- **Duplicated Blocks**: {}\n", duplication));
-        report.push_str(&format!("- **Duplication Score**: {:.2}\n", result.dry.duplication_score));
-        Self::append_context(&mut report, &dominant_lang, "dry", &duplication);
-        report.push('\n');
-
-        if let Some(llm_summary) = &result.llm_summary {
-            report.push_str("## 9. LLM Analysis Insights\n");
-            report.push_str(llm_summary);
+        if emit(ReportSection::TechStack) {
+            // 0. Tech Stack & Architecture
+            report.push_str("## 0. Tech Stack & Architecture\n");
+            report.push_str(&format!("- **Architecture Pattern**: {}\n", Self::format_val(&result.architecture.pattern)));
+            if !result.architecture.layers.is_empty() {
+                report.push_str(&format!("- **Architecture Layers**: {}\n", Self::format_bullet_list(&result.architecture.layers)));
+            }
+            report.push_str(&format!("- **Frameworks**: {}\n", Self::format_bullet_list(&result.tech_stack.frameworks)));
+            report.push_str(&format!("- **Databases**: {}\n", Self::format_bullet_list(&result.tech_stack.databases)));
+            report.push_str(&format!("- **Libraries**: {}\n", Self::format_bullet_list(&result.tech_stack.libraries)));
+            report.push_str(&format!("- **Build Tools**: {}\n", Self::format_bullet_list(&result.tech_stack.build_tools)));
+            report.push('\n');
+        }
+
+        if emit(ReportSection::DependencyInjection) {
+            // 2. Dependency Injection (DI) & Coupling
+            report.push_str("## 2. Dependency Injection (DI) & Coupling\n");
+            let di_pattern = Self::format_bullet_list(&result.di.injection_patterns);
+            report.push_str(&format!("- **Injection Pattern**: {}\n", di_pattern));
+            report.push_str(&format!("- **Abstraction Level**: {:.2}\n", result.di.abstraction_level));
+            report.push_str(&format!("- **Global State Dependency**: {}\n", Self::format_bullet_list(&result.di.global_state_usage)));
+            Self::append_context(&mut report, locale, &dominant_lang, "di", &di_pattern);
+            report.push('\n');
+        }
+
+        if emit(ReportSection::Testing) {
+            // 3. Testing Culture & Style
+            report.push_str("## 3. Testing Culture & Style\n");
+            for (lang, findings) in &sections {
+                Self::push_language_heading(&mut report, lang, findings, sections.len());
+                let testing = &findings.testing;
+                report.push_str(&format!("- **Test Location**: {}\n", Self::format_val(&testing.test_location)));
+                report.push_str(&format!("- **Mocking Strategy**: {}\n", Self::format_val(&testing.mocking_strategy)));
+                report.push_str(&format!("- **Naming Pattern**: {}\n", Self::format_val(&testing.naming_pattern)));
+                report.push_str(&format!("- **Assertion Style**: {}\n", Self::format_val(&testing.assertion_style)));
+                let testing_pattern = format!("{} {} {}", testing.test_location, testing.mocking_strategy, testing.assertion_style);
+                Self::append_context(&mut report, locale, lang, "testing", &testing_pattern);
+                report.push('\n');
+            }
+        }
+
+        if emit(ReportSection::Configuration) {
+            // 4. Configuration & Environment Management
+            report.push_str("## 4. Configuration & Environment Management\n");
+            report.push_str(&format!("- **Config Source**: {}\n", Self::format_bullet_list(&result.config.config_sources)));
+            report.push_str(&format!("- **Type Safety**: {}\n", Self::format_val(&result.config.type_safety)));
+            report.push_str(&format!("- **Secret Handling**: {}\n", Self::format_val(&result.config.secret_handling)));
+            Self::append_context(&mut report, locale, &dominant_lang, "config", &result.config.type_safety);
+            report.push('\n');
+        }
+
+        if emit(ReportSection::Security) {
+            // 5. Security & Safety Baseline
+            report.push_str("## 5. Security & Safety Baseline\n");
+            let secrets_found = if result.security.hardcoded_secrets.is_empty() { "None detected" } else { "Potential secrets found" };
+            let secret_locations: Vec<String> = result.security.hardcoded_secrets.iter()
+                .map(|secret| format!("`{}:{}:{}` {} ({}): `{}`", secret.file, secret.line, secret.column, secret.message, secret.rule_id, secret.redacted_preview))
+                .collect();
+            if secret_locations.is_empty() {
+                report.push_str(&format!("- **Hardcoded Secrets**: {}\n", secrets_found));
+            } else {
+                report.push_str(&format!("- **Hardcoded Secrets**: {}\n", Self::format_bullet_list(&secret_locations)));
+            }
+            if result.security.baselined_secrets > 0 {
+                report.push_str(&format!("- **Baselined Secrets**: {} accepted finding(s) hidden by `.buddy-secrets-baseline.json`\n", result.security.baselined_secrets));
+            }
+            report.push_str(&format!("- **Input Sanitization**: {}\n", Self::format_val(&result.security.input_sanitization)));
+            report.push_str(&format!("- **Memory Safety**: {}\n", Self::format_val(&result.security.memory_safety)));
+            if !result.security.unsafe_blocks.is_empty() {
+                let blocks: Vec<String> = result.security.unsafe_blocks.iter()
+                    .map(|block| format!("`{}:{}`", block.file, block.line))
+                    .collect();
+                report.push_str(&format!("- **Unsafe Blocks**:{}\n", Self::format_audit_list(&blocks)));
+            }
+            report.push_str(&format!("- **Concurrency Safety**: {}\n", Self::format_val(&result.security.concurrency_safety)));
+            Self::append_context(&mut report, locale, &dominant_lang, "security", secrets_found);
+            report.push('\n');
+        }
+
+        if emit(ReportSection::ErrorHandling) {
+            // 6. Error Handling Strategy
+            report.push_str("## 6. Error Handling Strategy\n");
+            for (lang, findings) in &sections {
+                Self::push_language_heading(&mut report, lang, findings, sections.len());
+                let error_handling = &findings.error_handling;
+                let error_pattern = Self::format_bullet_list(&Self::counted_patterns(error_handling));
+                report.push_str(&format!("- **Failure Pattern**: {}\n", error_pattern));
+                let (wrapped, formatted) = (error_handling.count_of("fmt.Errorf with %w"), error_handling.count_of("fmt.Errorf with %v"));
+                if wrapped + formatted > 0 {
+                    report.push_str(&format!(
+                        "- **Error Wrapping**: {} of `fmt.Errorf` calls that include an error wrap it with `%w` ({} of {})\n",
+                        Self::format_share(wrapped, wrapped + formatted), wrapped, wrapped + formatted
+                    ));
+                }
+                let inspected = error_handling.count_of("errors.Is") + error_handling.count_of("errors.As");
+                let compared = error_handling.count_of("Direct comparison (err == ErrX)");
+                if inspected + compared > 0 {
+                    report.push_str(&format!(
+                        "- **Error Inspection**: {} of error checks use `errors.Is`/`errors.As` rather than `==` ({} of {})\n",
+                        Self::format_share(inspected, inspected + compared), inspected, inspected + compared
+                    ));
+                }
+                if !error_handling.unwrap_usage.is_empty() {
+                    let files: Vec<String> = error_handling.unwrap_usage.iter()
+                        .map(|usage| format!("`{}`: {} unwrap(), {} expect()", usage.file, usage.unwrap_calls, usage.expect_calls))
+                        .collect();
+                    report.push_str(&format!("- **unwrap()/expect() Calls**:{}\n", Self::format_audit_list(&files)));
+                }
+                report.push_str(&format!("- **Logging Consistency**: {}\n", Self::format_val(&error_handling.logging_consistency)));
+                if !error_handling.logging.stray_prints.is_empty() {
+                    let prints: Vec<String> = error_handling.logging.stray_prints.iter()
+                        .map(|print| format!("`{}:{}` {}", print.file, print.line, print.call))
+                        .collect();
+                    report.push_str(&format!("- **Stray Prints**:{}\n", Self::format_audit_list(&prints)));
+                }
+                let error_context = format!("{}\n{}", error_pattern, error_handling.logging_consistency);
+                let (mut context, mut recommendation) = Self::context_keys(lang, "error_handling", &error_context);
+                if lang == "Go" && wrapped + formatted > 0 {
+                    // Only advise `%w` where most errors are still formatted with `%v`.
+                    if wrapped * 100 < (wrapped + formatted) * MIN_WRAPPED_ERRORS_PERCENT {
+                        recommendation.insert(0, "go.error_handling.err_check.recommendation");
+                    } else {
+                        context.push("go.error_handling.wrapping.context");
+                    }
+                }
+                Self::push_context(&mut report, locale, context, recommendation);
+                report.push('\n');
+            }
+        }
+
+        if emit(ReportSection::DesignPatterns) {
+            // 7. Design Patterns
+            report.push_str("## 7. Design Patterns\n");
+            let patterns = Self::format_bullet_list(&result.design_patterns.patterns);
+            report.push_str(&format!("- **Detected Patterns**: {}\n", patterns));
+            Self::append_context(&mut report, locale, &dominant_lang, "design_patterns", &patterns);
+            report.push('\n');
+        }
+
+        if emit(ReportSection::Dry) {
+            // 8. DRY Analysis
+            report.push_str("## 8. DRY Analysis\n");
+            let duplication = if result.dry.duplicated_blocks.is_empty() {
+                "No significant duplication detected".to_string()
+            } else {
+                let blocks: Vec<String> = result.dry.duplicated_blocks.iter()
+                    .map(|block| format!("`{}:{}` {}", block.file, block.line, block.description))
+                    .collect();
+                Self::format_bullet_list(&blocks)
+            };
+            report.push_str(&format!("- **Duplicated Blocks**: {}\n", duplication));
+            report.push_str(&format!("- **Duplication Score**: {:.2}\n", result.dry.duplication_score));
+            report.push_str(&format!("- **Duplicated Code**: {:.1}% of tokens\n", result.dry.duplication_percentage));
+            if !result.dry.clones.is_empty() {
+                report.push_str("- **Code Clones**:\n");
+                for clone in result.dry.clones.iter().take(MAX_LISTED_CLONES) {
+                    report.push_str(&format!(
+                        "  - `{}:{}-{}` duplicates `{}:{}-{}` ({}, {} tokens)\n",
+                        clone.duplicate.file, clone.duplicate.start_line, clone.duplicate.end_line,
+                        clone.original.file, clone.original.start_line, clone.original.end_line,
+                        clone.clone_type.as_str(), clone.token_count
+                    ));
+                }
+                if result.dry.clones.len() > MAX_LISTED_CLONES {
+                    report.push_str(&format!("  - ...and {} more\n", result.dry.clones.len() - MAX_LISTED_CLONES));
+                }
+            }
+            Self::append_context(&mut report, locale, &dominant_lang, "dry", &duplication);
             report.push('\n');
         }
 
+        if emit(ReportSection::Llm) {
+            if let Some(llm_summary) = &result.llm_summary {
+                report.push_str("## 9. LLM Analysis Insights\n");
+                report.push_str(llm_summary);
+                report.push('\n');
+            }
+        }
+
         report
     }
 
@@ -134,22 +347,120 @@ impl ReportGenerator {
             .unwrap_or_else(|| "Unknown".to_string())
     }
 
-    fn get_build_commands(lang: &str, _result: &AnalysisResult) -> String {
+    /// Languages that get their own subsection: every language with at least
+    /// `MIN_LANGUAGE_SECTION_FILES` files, most files first. Falls back to the
+    /// repository-wide findings under the dominant language otherwise.
+    fn language_sections(result: &AnalysisResult, dominant_lang: &str) -> Vec<(String, LanguageFindings)> {
+        let mut sections: Vec<(String, LanguageFindings)> = result.languages.iter()
+            .filter(|(_, findings)| findings.file_count >= MIN_LANGUAGE_SECTION_FILES)
+            .map(|(lang, findings)| (lang.as_str().to_string(), findings.clone()))
+            .collect();
+        sections.sort_by_key(|(_, findings)| std::cmp::Reverse(findings.file_count));
+
+        if sections.is_empty() {
+            sections.push((dominant_lang.to_string(), LanguageFindings {
+                file_count: result.language_counts.values().sum(),
+                naming: result.naming.clone(),
+                error_handling: result.error_handling.clone(),
+                testing: result.testing.clone(),
+            }));
+        }
+        sections
+    }
+
+    fn push_language_heading(report: &mut String, lang: &str, findings: &LanguageFindings, section_count: usize) {
+        if section_count > 1 {
+            report.push_str(&format!("### {} ({} files)\n", lang, findings.file_count));
+        }
+    }
+
+    /// Summary of a `--since` run: which files changed and whether they break
+    /// the conventions described in the rest of the report.
+    fn format_diff(diff: &DiffAnalysis, violations: &NamingViolations) -> String {
+        let mut section = format!("## Changes Since `{}`\n", diff.base_ref);
+        section.push_str(&format!(
+            "- **Changed Files**: {} (secrets and duplication below are limited to these files)\n",
+            diff.changed_files.len()
+        ));
+        if violations.is_empty() {
+            section.push_str("- **Convention Deviations**: None, the changed code follows the detected naming conventions\n");
+        } else {
+            section.push_str(&format!("- **Convention Deviations**: {}, see Naming Violations\n", violations.total));
+        }
+        section.push('\n');
+        section
+    }
+
+    /// One line per identifier breaking the dominant casing for its kind.
+    fn format_violations(violations: &NamingViolations) -> String {
+        let mut list: String = violations.violations.iter()
+            .map(|v| format!("- `{}:{}` {} `{}` is {}, expected {}\n", v.file, v.line, v.kind.as_str(), v.name, v.found, v.expected))
+            .collect();
+        if violations.total > violations.violations.len() {
+            list.push_str(&format!("- ...and {} more\n", violations.total - violations.violations.len()));
+        }
+        list
+    }
+
+    /// Findings grouped by the section their rule declares.
+    fn format_custom_rules(custom_rules: &CustomRuleAnalysis) -> String {
+        let mut groups: BTreeMap<&str, Vec<&CustomRuleFinding>> = BTreeMap::new();
+        for finding in &custom_rules.findings {
+            groups.entry(&finding.section).or_default().push(finding);
+        }
+
+        let mut section = String::new();
+        for (heading, findings) in groups {
+            section.push_str(&format!("### {}\n", heading));
+            for finding in findings.iter().take(MAX_LISTED_RULE_FINDINGS) {
+                section.push_str(&format!(
+                    "- `{}:{}:{}` {} `{}`: {}\n",
+                    finding.file, finding.line, finding.column, finding.severity.as_str(), finding.rule_id, finding.message
+                ));
+            }
+            if findings.len() > MAX_LISTED_RULE_FINDINGS {
+                section.push_str(&format!("- ...and {} more\n", findings.len() - MAX_LISTED_RULE_FINDINGS));
+            }
+            section.push('\n');
+        }
+        section
+    }
+
+    /// Commands of `kind` the project defines, with the file each came from.
+    fn format_project_commands(result: &AnalysisResult, kind: CommandKind) -> String {
+        result.commands.of_kind(kind)
+            .map(|c| format!("- `{}`: from `{}`\n", c.command, c.source))
+            .collect()
+    }
+
+    /// Falls back to the usual commands for the dominant language when the
+    /// project defines no build scripts of its own.
+    fn get_build_commands(lang: &str, result: &AnalysisResult) -> String {
+        let detected = Self::format_project_commands(result, CommandKind::Build);
+        if !detected.is_empty() {
+            return detected;
+        }
         match lang {
             "Go" => "- `go build ./...`: Build all packages\n- `go mod tidy`: Clean up dependencies\n".to_string(),
             "Rust" => "- `cargo build`: Build the project\n- `cargo build --release`: Build for production\n".to_string(),
             "TypeScript" | "JavaScript" => "- `npm run build` or `yarn build`: Build the project\n- `npm install`: Install dependencies\n".to_string(),
             "Python" => "- `pip install -r requirements.txt`: Install dependencies\n- `python setup.py build`: Build if applicable\n".to_string(),
+            "Java" => "- `mvn package` or `./gradlew build`: Build the project\n".to_string(),
             _ => "- N/A: No build command detected\n".to_string(),
         }
     }
 
-    fn get_test_commands(lang: &str, _result: &AnalysisResult) -> String {
+    fn get_test_commands(lang: &str, result: &AnalysisResult) -> String {
+        let detected = Self::format_project_commands(result, CommandKind::Test);
+        if !detected.is_empty() {
+            return detected;
+        }
         match lang {
             "Go" => "- `go test ./...`: Run all tests\n- `go test -v ./...`: Run tests with verbose output\n".to_string(),
             "Rust" => "- `cargo test`: Run all tests\n- `cargo test -- --nocapture`: Run tests with stdout enabled\n".to_string(),
-            "TypeScript" | "JavaScript" => "- `npm test` or `yarn test`: Run tests\n- `npm run test:watch`: Run tests in watch mode\n".to_string(),
+            "TypeScript" | "JavaScript" => "- `npm test` or `yarn test`: Run tests\n".to_string(),
             "Python" => "- `pytest`: Run tests using pytest\n- `python -m unittest discover`: Run tests using unittest\n".to_string(),
+            "Java" => "- `mvn test` or `./gradlew test`: Run tests\n".to_string(),
             _ => "- N/A: No test command detected\n".to_string(),
         }
     }
@@ -162,7 +473,38 @@ impl ReportGenerator {
         }
     }
 
-    fn format_bullet_list(list: &[String]) -> String {
+    fn format_casing(histogram: &CasingHistogram) -> String {
+        let total = histogram.total();
+        if total == 0 {
+            return Casing::Unknown.to_string();
+        }
+        format!("{} ({:.0}% of {} identifiers)", histogram.dominant(), histogram.confidence(), total)
+    }
+
+    /// Failure patterns, with how often they occur where the analyzer counts them.
+    fn counted_patterns(error_handling: &ErrorHandlingAnalysis) -> Vec<String> {
+        error_handling.failure_patterns.iter()
+            .map(|pattern| match error_handling.pattern_counts.get(pattern) {
+                Some(count) => format!("{} ({})", pattern, count),
+                None => pattern.clone(),
+            })
+            .collect()
+    }
+
+    fn format_share(part: usize, total: usize) -> String {
+        format!("{:.0}%", part as f64 * 100.0 / total as f64)
+    }
+
+    /// Like `format_bullet_list`, capped at `MAX_LISTED_AUDIT_ITEMS`.
+    fn format_audit_list(list: &[String]) -> String {
+        let mut s = Self::format_bullet_list(&list[..list.len().min(MAX_LISTED_AUDIT_ITEMS)]);
+        if list.len() > MAX_LISTED_AUDIT_ITEMS {
+            s.push_str(&format!("\n  - ...and {} more", list.len() - MAX_LISTED_AUDIT_ITEMS));
+        }
+        s
+    }
+
+    fn format_bullet_list<T: std::fmt::Display>(list: &[T]) -> String {
         if list.is_empty() {
             "N/A".to_string()
         } else {
@@ -174,109 +516,303 @@ impl ReportGenerator {
         }
     }
 
-    fn append_context(report: &mut String, lang: &str, aspect: &str, found_pattern: &str) {
-        let (context, recommendation): (Option<String>, Option<String>) = match (lang, aspect) {
+    fn append_context(report: &mut String, locale: Locale, lang: &str, aspect: &str, found_pattern: &str) {
+        let (context, recommendation) = Self::context_keys(lang, aspect, found_pattern);
+        Self::push_context(report, locale, context, recommendation);
+    }
+
+    /// Message keys of the context and recommendations for what was found.
+    fn context_keys(lang: &str, aspect: &str, found_pattern: &str) -> (Vec<&'static str>, Vec<&'static str>) {
+        let (context, mut recommendation): (Vec<&'static str>, Vec<&'static str>) = match (lang, aspect) {
             ("Go", "error_handling") => {
+                let mut ctx = Vec::new();
+                let mut rec = Vec::new();
                 if found_pattern.contains("if err != nil") {
-                    (
-                        Some("Pola ini adalah standar idiomatis dalam bahasa Go untuk memastikan kegagalan ditangani secara eksplisit.".to_string()),
-                        Some("Pastikan untuk membungkus error (*error wrapping*) menggunakan `%w` pada `fmt.Errorf` untuk mempertahankan *stack trace* atau konteks error saat dikembalikan ke pemanggil.".to_string())
-                    )
-                } else { (None, None) }
-            },
-            ("Go", "di") => {
-                if found_pattern.contains("NewXXX") {
-                    (
-                        Some("Menggunakan factory function (`NewXXX`) untuk inisialisasi struct adalah pola umum di Go untuk mendukung dependency injection.".to_string()),
-                        Some("Pertimbangkan untuk menerima interface daripada struct konkret dalam constructor untuk meningkatkan *testability* dan modularitas.".to_string())
-                    )
-                } else { (None, None) }
-            },
-            ("Go", "testing") => {
-                if found_pattern.contains("gomock") {
-                    (
-                        Some("Penggunaan `gomock` menunjukkan budaya testing yang matang dengan penggunaan mock objects yang tergenerasi.".to_string()),
-                        Some("Pastikan mock diupdate setiap kali ada perubahan pada interface menggunakan `mockgen`.".to_string())
-                    )
-                } else { (None, None) }
-            },
-            ("Go", "config") => {
-                if found_pattern.contains("properties.yaml") {
-                    (
-                        Some("Penggunaan `properties.yaml` menunjukkan adaptasi pola konfigurasi terstruktur mirip Spring Boot.".to_string()),
-                        Some("Pastikan konfigurasi di-load ke dalam struct yang ter-validate untuk menjamin type safety saat runtime.".to_string())
-                    )
-                } else { (None, None) }
+                    ctx.push("go.error_handling.err_check.context");
+                }
+                if found_pattern.contains("Direct comparison") {
+                    rec.push("go.error_handling.inspect.recommendation");
+                }
+                if found_pattern.contains("pkg/errors") {
+                    rec.push("go.error_handling.pkg_errors.recommendation");
+                }
+                if found_pattern.contains("Discarded errors") {
+                    rec.push("go.error_handling.discarded.recommendation");
+                }
+                (ctx, rec)
             },
+            ("Go", "di") if found_pattern.contains("NewXXX") => (
+                vec!["go.di.constructor.context"],
+                vec!["go.di.constructor.recommendation"],
+            ),
+            ("Go", "testing") if found_pattern.contains("gomock") => (
+                vec!["go.testing.gomock.context"],
+                vec!["go.testing.gomock.recommendation"],
+            ),
+            ("Go", "config") if found_pattern.contains("properties.yaml") => (
+                vec!["go.config.properties.context"],
+                vec!["go.config.properties.recommendation"],
+            ),
             ("Go", "design_patterns") => {
                 let mut ctx = Vec::new();
                 let mut rec = Vec::new();
                 if found_pattern.contains("Factory") {
-                    ctx.push("Factory pattern digunakan untuk enkapsulasi inisialisasi objek kompleks.");
+                    ctx.push("go.design_patterns.factory.context");
                 }
                 if found_pattern.contains("Singleton") {
-                    ctx.push("Singleton digunakan untuk akses global ke resource tunggal (seperti DB connection).");
-                    rec.push("Hati-hati dengan Singleton dalam pengujian paralel; pertimbangkan dependency injection sebagai alternatif.");
+                    ctx.push("go.design_patterns.singleton.context");
+                    rec.push("go.design_patterns.singleton.recommendation");
                 }
                 if found_pattern.contains("Strategy") {
-                    ctx.push("Strategy pattern diimplementasikan melalui interfaces untuk fleksibilitas algoritma.");
+                    ctx.push("go.design_patterns.strategy.context");
                 }
-                
-                (
-                    if ctx.is_empty() { None } else { Some(ctx.join(" ")) },
-                    if rec.is_empty() { None } else { Some(rec.join(" ")) }
-                )
+                (ctx, rec)
             },
-            ("Go", "naming") => {
-                if found_pattern.contains("PascalCase") {
-                    (
-                        Some("PascalCase di Go digunakan untuk mengekspor (export) simbol agar bisa diakses dari package lain.".to_string()),
-                        Some("Gunakan `camelCase` untuk internal (unexported) variabel dan fungsi guna menjaga enkapsulasi package.".to_string())
-                    )
-                } else { (None, None) }
+            ("Go", "naming") if found_pattern.contains("PascalCase") => (
+                vec!["go.naming.pascal.context"],
+                vec!["go.naming.pascal.recommendation"],
+            ),
+            ("Go", "dry") => (
+                vec!["go.dry.context"],
+                vec!["go.dry.recommendation"],
+            ),
+            ("Python", "naming") if found_pattern.contains("snake_case") => (
+                vec!["python.naming.snake.context"],
+                vec!["python.naming.snake.recommendation"],
+            ),
+            ("TypeScript", "naming") | ("JavaScript", "naming") if found_pattern.contains("camelCase") => (
+                vec!["js.naming.camel.context"],
+                vec!["js.naming.camel.recommendation"],
+            ),
+            ("Python", "error_handling") => {
+                let mut ctx = Vec::new();
+                let mut rec = Vec::new();
+                let catches = ["Bare except", "except Exception", "Specific exceptions"].iter().any(|p| found_pattern.contains(p));
+                if catches {
+                    ctx.push("python.error_handling.context");
+                }
+                if found_pattern.contains("Custom exception hierarchy") {
+                    ctx.push("python.error_handling.custom.context");
+                }
+                if found_pattern.contains("Bare except") || found_pattern.contains("except Exception") {
+                    rec.push("python.error_handling.recommendation");
+                }
+                if catches && !found_pattern.contains("raise ... from") {
+                    rec.push("python.error_handling.chaining.recommendation");
+                }
+                (ctx, rec)
             },
-            ("Go", "dry") => {
-                (
-                    Some("Prinsip DRY (Don't Repeat Yourself) sangat penting untuk menjaga kemudahan pemeliharaan kode Go.".to_string()),
-                    Some("Gunakan fungsi utilitas atau helper untuk logika yang berulang, terutama jika logika tersebut digunakan di beberapa package.".to_string())
-                )
+            ("Python", "di") => {
+                let mut ctx = Vec::new();
+                let mut rec = Vec::new();
+                if found_pattern.contains("FastAPI Depends") {
+                    ctx.push("python.di.depends.context");
+                    rec.push("python.di.depends.recommendation");
+                }
+                if found_pattern.contains("dependency-injector") {
+                    ctx.push("python.di.container.context");
+                }
+                if found_pattern.contains("__init__") {
+                    ctx.push("python.di.constructor.context");
+                }
+                (ctx, rec)
             },
-            ("Python", "naming") => {
-                if found_pattern.contains("snake_case") {
-                    (
-                        Some("Python mengikuti PEP 8 yang merekomendasikan `snake_case` untuk variabel dan fungsi.".to_string()),
-                        Some("Pastikan untuk konsisten menggunakan `PascalCase` hanya untuk nama Class.".to_string())
-                    )
-                } else { (None, None) }
+            ("Python", "testing") if found_pattern.contains("unittest (") => (
+                vec!["python.testing.unittest.context"],
+                vec!["python.testing.unittest.recommendation"],
+            ),
+            ("Python", "testing") if found_pattern.contains("pytest") => (
+                vec!["python.testing.pytest.context"],
+                vec!["python.testing.pytest.recommendation"],
+            ),
+            ("Python", "design_patterns") => {
+                let mut ctx = Vec::new();
+                let mut rec = Vec::new();
+                if found_pattern.contains("Abstract Base Classes") || found_pattern.contains("Protocol") {
+                    ctx.push("python.design_patterns.abc.context");
+                }
+                if found_pattern.contains("Decorator") {
+                    ctx.push("python.design_patterns.decorator.context");
+                }
+                if found_pattern.contains("Singleton") {
+                    ctx.push("python.design_patterns.singleton.context");
+                    rec.push("python.design_patterns.singleton.recommendation");
+                }
+                (ctx, rec)
             },
-            ("TypeScript", "naming") | ("JavaScript", "naming") => {
-                if found_pattern.contains("camelCase") {
-                    (
-                        Some("JavaScript/TypeScript standar menggunakan `camelCase` untuk variabel dan fungsi.".to_string()),
-                        Some("Gunakan `PascalCase` untuk Class dan Interfaces, serta `UPPER_SNAKE_CASE` untuk konstanta global.".to_string())
-                    )
-                } else { (None, None) }
+            ("TypeScript", "di") | ("JavaScript", "di") if found_pattern != "N/A" => (
+                vec!["js.di.context"],
+                vec!["js.di.recommendation"],
+            ),
+            ("Rust", "error_handling") => {
+                let mut ctx = Vec::new();
+                let mut rec = Vec::new();
+                if found_pattern.contains("? propagation") {
+                    ctx.push("rust.error_handling.propagation.context");
+                }
+                if found_pattern.contains("thiserror") || found_pattern.contains("anyhow") {
+                    ctx.push("rust.error_handling.crates.context");
+                }
+                if found_pattern.contains("unwrap()/expect()") {
+                    rec.push("rust.error_handling.unwrap.recommendation");
+                }
+                (ctx, rec)
             },
-            ("Python", "error_handling") => {
-                (
-                    Some("Python mengandalkan EAFP (*Easier to Ask for Forgiveness than Permission*) menggunakan blok `try-except`.".to_string()),
-                    Some("Gunakan exception yang spesifik daripada menangkap `Exception` umum untuk menghindari penanganan error yang tidak disengaja.".to_string())
-                )
+            ("Rust", "di") if found_pattern.contains("dyn Trait") || found_pattern.contains("Generic Bounds") => (
+                vec!["rust.di.traits.context"],
+                Vec::new(),
+            ),
+            ("TypeScript", "error_handling") | ("JavaScript", "error_handling") => {
+                let mut ctx = Vec::new();
+                let mut rec = Vec::new();
+                if found_pattern.contains("Result types") || found_pattern.contains("Either") {
+                    ctx.push("js.error_handling.result.context");
+                    rec.push("js.error_handling.result.recommendation");
+                }
+                if found_pattern.contains("Custom Error subclasses") {
+                    ctx.push("js.error_handling.custom.context");
+                }
+                if found_pattern.contains("try/catch") && found_pattern.contains(".catch()") {
+                    rec.push("js.error_handling.async.recommendation");
+                }
+                (ctx, rec)
             },
-            ("TypeScript", "di") | ("JavaScript", "di") => {
-                (
-                    Some("Di ekosistem JS/TS, Constructor Injection sering digunakan terutama dengan framework seperti NestJS atau Inversify.".to_string()),
-                    Some("Manfaatkan TypeScript Interfaces untuk decoupling antara consumer dan provider agar lebih mudah di-mock saat unit testing.".to_string())
-                )
+            ("TypeScript", "testing") | ("JavaScript", "testing") => {
+                let mut ctx = Vec::new();
+                let mut rec = Vec::new();
+                if found_pattern.contains("jest.mock") || found_pattern.contains("vi.mock") {
+                    ctx.push("js.testing.module_mock.context");
+                    rec.push("js.testing.module_mock.recommendation");
+                }
+                if found_pattern.contains("sinon") {
+                    rec.push("js.testing.sinon.recommendation");
+                }
+                (ctx, rec)
             },
-            _ => (None, None)
+            _ => (Vec::new(), Vec::new()),
         };
+        // The logging verdict is worded the same for every language.
+        if aspect == "error_handling" {
+            if found_pattern.contains("Mixed: ") {
+                recommendation.push("logging.mixed.recommendation");
+            }
+            if found_pattern.contains("stray print") {
+                recommendation.push("logging.stray_prints.recommendation");
+            }
+        }
+        (context, recommendation)
+    }
 
-        if let Some(ctx) = context {
-            report.push_str(&format!("- **Context**: {}\n", ctx));
+    fn push_context(report: &mut String, locale: Locale, context: Vec<&'static str>, recommendation: Vec<&'static str>) {
+        let translate = |keys: Vec<&'static str>| -> Vec<&'static str> {
+            keys.into_iter().map(|key| i18n::message(locale, key)).collect()
+        };
+        let context = translate(context);
+        let recommendation = translate(recommendation);
+
+        if !context.is_empty() {
+            report.push_str(&format!("- **Context**: {}\n", context.join(" ")));
+        }
+        if !recommendation.is_empty() {
+            report.push_str(&format!("- **Best Practice Recommendation**: {}\n", recommendation.join(" ")));
         }
-        if let Some(rec) = recommendation {
-            report.push_str(&format!("- **Best Practice Recommendation**: {}\n", rec));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::SupportedLanguage;
+    use crate::rules::naming::{IdentifierKind, NamingViolation};
+
+    #[test]
+    fn test_polyglot_code_style_sections() {
+        let mut result = AnalysisResult::default();
+        let go = result.languages.entry(SupportedLanguage::Go).or_default();
+        go.file_count = 5;
+        go.naming.record_function(Casing::PascalCase);
+        let ts = result.languages.entry(SupportedLanguage::TypeScript).or_default();
+        ts.file_count = 4;
+        ts.naming.record_function(Casing::CamelCase);
+        let py = result.languages.entry(SupportedLanguage::Python).or_default();
+        py.file_count = 1;
+        result.language_counts.insert("Go".to_string(), 5);
+        result.language_counts.insert("TypeScript".to_string(), 4);
+        result.language_counts.insert("Python".to_string(), 1);
+
+        let report = ReportGenerator::generate(&result, Locale::En);
+        let go_pos = report.find("### Go (5 files)").unwrap();
+        let ts_pos = report.find("### TypeScript (4 files)").unwrap();
+        assert!(go_pos < ts_pos);
+        assert!(!report.contains("### Python"));
+        assert!(report.contains("- **Function Casing**: PascalCase (100% of 1 identifiers)"));
+        assert!(report.contains("- **Function Casing**: camelCase (100% of 1 identifiers)"));
+    }
+
+    #[test]
+    fn test_truncated_naming_violations() {
+        let violation = |file: &str, line, name: &str| NamingViolation {
+            file: file.to_string(),
+            line,
+            kind: IdentifierKind::Function,
+            name: name.to_string(),
+            expected: Casing::SnakeCase,
+            found: Casing::CamelCase,
+        };
+        let mut result = AnalysisResult {
+            naming_violations: NamingViolations::new(vec![
+                violation("views.py", 9, "renderList"),
+                violation("views.py", 4, "renderDetail"),
+                violation("forms.py", 2, "validateForm"),
+            ]),
+            ..Default::default()
+        };
+        result.naming_violations.truncate(2);
+
+        let report = ReportGenerator::generate(&result, Locale::En);
+        assert!(report.contains("## Naming Violations\n- `forms.py:2` function `validateForm` is camelCase, expected snake_case\n- `views.py:4` function `renderDetail`"));
+        assert!(!report.contains("renderList"));
+        assert!(report.contains("- ...and 1 more\n"));
+    }
+
+    #[test]
+    fn test_python_error_handling_advice_follows_findings() {
+        let mut result = AnalysisResult::default();
+        result.language_counts.insert("Python".to_string(), 3);
+        let report = ReportGenerator::generate(&result, Locale::En);
+        assert!(!report.contains("EAFP"));
+
+        result.error_handling.failure_patterns = vec!["Bare except".to_string(), "Exception chaining (raise ... from)".to_string()];
+        let report = ReportGenerator::generate(&result, Locale::En);
+        assert!(report.contains("EAFP"));
+        assert!(report.contains("Catch specific exceptions"));
+        assert!(!report.contains("raise NewError(...) from err"));
+    }
+
+    #[test]
+    fn test_go_wrapping_advice_follows_practice() {
+        let mut result = AnalysisResult::default();
+        result.language_counts.insert("Go".to_string(), 3);
+        for _ in 0..3 {
+            result.error_handling.count("fmt.Errorf with %w");
+        }
+        let report = ReportGenerator::generate(&result, Locale::En);
+        assert!(report.contains("- **Error Wrapping**: 100% of `fmt.Errorf` calls that include an error wrap it with `%w` (3 of 3)"));
+        assert!(report.contains("  - fmt.Errorf with %w (3)"));
+        assert!(!report.contains("Wrap errors with `%w`"));
+
+        result.error_handling.count("fmt.Errorf with %v");
+        let report = ReportGenerator::generate(&result, Locale::En);
+        assert!(report.contains("75% of `fmt.Errorf` calls"));
+        assert!(!report.contains("Wrap errors with `%w`"));
+        assert!(report.contains("Errors are already wrapped with `%w`"));
+
+        for _ in 0..3 {
+            result.error_handling.count("fmt.Errorf with %v");
         }
+        let report = ReportGenerator::generate(&result, Locale::En);
+        assert!(report.contains("43% of `fmt.Errorf` calls"));
+        assert!(report.contains("Wrap errors with `%w`"));
+        assert!(!report.contains("Errors are already wrapped with `%w`"));
     }
 }
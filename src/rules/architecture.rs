@@ -1,4 +1,5 @@
 use serde::{Serialize, Deserialize};
+use super::{merge_first, merge_unique, Merge};
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct ArchitectureAnalysis {
@@ -6,3 +7,11 @@ pub struct ArchitectureAnalysis {
     pub layers: Vec<String>,
     pub modules: Vec<String>,
 }
+
+impl Merge for ArchitectureAnalysis {
+    fn merge(&mut self, other: Self) {
+        merge_first(&mut self.pattern, other.pattern);
+        merge_unique(&mut self.layers, other.layers);
+        merge_unique(&mut self.modules, other.modules);
+    }
+}
@@ -0,0 +1,37 @@
+use serde::{Serialize, Deserialize};
+use super::Merge;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CommandKind {
+    Build,
+    Test,
+    Lint,
+    Other,
+}
+
+/// A command the project itself defines, e.g. a `package.json` script or a Makefile target.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectCommand {
+    pub command: String,
+    pub kind: CommandKind,
+    /// Repository-relative path of the file the command was read from.
+    pub source: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct CommandAnalysis {
+    pub commands: Vec<ProjectCommand>,
+}
+
+impl CommandAnalysis {
+    pub fn of_kind(&self, kind: CommandKind) -> impl Iterator<Item = &ProjectCommand> {
+        self.commands.iter().filter(move |c| c.kind == kind)
+    }
+}
+
+impl Merge for CommandAnalysis {
+    /// Order and duplicates are sorted out by `ScriptAnalyzer::finalize`.
+    fn merge(&mut self, other: Self) {
+        self.commands.extend(other.commands);
+    }
+}
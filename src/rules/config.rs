@@ -1,4 +1,5 @@
 use serde::{Serialize, Deserialize};
+use super::{merge_first, merge_unique, Merge};
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct ConfigAnalysis {
@@ -6,3 +7,11 @@ pub struct ConfigAnalysis {
     pub type_safety: String,
     pub secret_handling: String,
 }
+
+impl Merge for ConfigAnalysis {
+    fn merge(&mut self, other: Self) {
+        merge_unique(&mut self.config_sources, other.config_sources);
+        merge_first(&mut self.type_safety, other.type_safety);
+        merge_first(&mut self.secret_handling, other.secret_handling);
+    }
+}
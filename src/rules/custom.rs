@@ -0,0 +1,48 @@
+use serde::{Serialize, Deserialize};
+use super::Merge;
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct CustomRuleAnalysis {
+    pub findings: Vec<CustomRuleFinding>,
+}
+
+/// A match of a project-defined rule, see `crate::custom_rules`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CustomRuleFinding {
+    /// File name of the rule without the `.scm` extension.
+    pub rule_id: String,
+    /// Filled in by `ProjectAnalyzer`; the rule engine only sees file content.
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub severity: Severity,
+    pub message: String,
+    /// Heading the finding is grouped under in the report.
+    pub section: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    #[default]
+    Warning,
+    Note,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        }
+    }
+}
+
+impl Merge for CustomRuleAnalysis {
+    /// Findings carry their file, so findings from different files never repeat.
+    fn merge(&mut self, other: Self) {
+        self.findings.extend(other.findings);
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Serialize, Deserialize};
+use super::{merge_unique, Merge};
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct DIAnalysis {
@@ -6,3 +7,13 @@ pub struct DIAnalysis {
     pub abstraction_level: f32, // Ratio of interface vs concrete types
     pub global_state_usage: Vec<String>,
 }
+
+impl Merge for DIAnalysis {
+    fn merge(&mut self, other: Self) {
+        merge_unique(&mut self.injection_patterns, other.injection_patterns);
+        if self.abstraction_level == 0.0 {
+            self.abstraction_level = other.abstraction_level;
+        }
+        merge_unique(&mut self.global_state_usage, other.global_state_usage);
+    }
+}
@@ -1,6 +1,13 @@
 use serde::{Serialize, Deserialize};
+use super::{merge_unique, Merge};
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct DesignPatternAnalysis {
     pub patterns: Vec<String>,
 }
+
+impl Merge for DesignPatternAnalysis {
+    fn merge(&mut self, other: Self) {
+        merge_unique(&mut self.patterns, other.patterns);
+    }
+}
@@ -0,0 +1,10 @@
+use serde::{Serialize, Deserialize};
+
+/// Scope of a `--since` run: per-file findings elsewhere in the result,
+/// naming violations included, are limited to `changed_files`, while
+/// conventions still come from the whole repository.
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct DiffAnalysis {
+    pub base_ref: String,
+    pub changed_files: Vec<String>,
+}
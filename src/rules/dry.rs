@@ -1,7 +1,73 @@
 use serde::{Serialize, Deserialize};
+use super::Merge;
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct DRYAnalysis {
-    pub duplicated_blocks: Vec<String>,
+    pub duplicated_blocks: Vec<DuplicatedBlock>,
+    /// Occurrences of string literals beyond the first in their file.
+    pub repeated_literals: usize,
+    /// Set from `repeated_literals` by `finalize_score` once all files are merged.
     pub duplication_score: f64,
+    /// Cross-file token clones, see `crate::clones`.
+    pub clones: Vec<CodeClone>,
+    /// Share of all source tokens that are part of at least one clone.
+    pub duplication_percentage: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct DuplicatedBlock {
+    /// Filled in by `ProjectAnalyzer`; language analyzers only see file content.
+    pub file: String,
+    pub line: usize,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CloneType {
+    /// Identical token sequences, ignoring whitespace and comments.
+    Type1,
+    /// Same structure with identifiers or literals renamed.
+    Type2,
+}
+
+impl CloneType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CloneType::Type1 => "Type-1",
+            CloneType::Type2 => "Type-2",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CloneLocation {
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CodeClone {
+    pub clone_type: CloneType,
+    pub token_count: usize,
+    pub original: CloneLocation,
+    pub duplicate: CloneLocation,
+}
+
+impl DRYAnalysis {
+    /// Each repeated literal adds 0.1. Computed from the integer count rather
+    /// than summed per file, since float addition depends on how the files
+    /// were grouped.
+    pub fn finalize_score(&mut self) {
+        self.duplication_score = self.repeated_literals as f64 * 0.1;
+    }
+}
+
+impl Merge for DRYAnalysis {
+    /// Blocks carry their file, so blocks from different files never repeat.
+    fn merge(&mut self, other: Self) {
+        self.duplicated_blocks.extend(other.duplicated_blocks);
+        self.repeated_literals += other.repeated_literals;
+        self.clones.extend(other.clones);
+    }
 }
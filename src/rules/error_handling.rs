@@ -1,7 +1,64 @@
+use std::collections::BTreeMap;
 use serde::{Serialize, Deserialize};
+use super::{merge_first, merge_unique, Merge};
+use super::logging::LoggingAnalysis;
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct ErrorHandlingAnalysis {
     pub failure_patterns: Vec<String>,
+    /// Verdict derived from `logging` once every file is analyzed.
     pub logging_consistency: String,
+    #[serde(default)]
+    pub logging: LoggingAnalysis,
+    /// How often each failure pattern occurs, for analyzers that count them.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pattern_counts: BTreeMap<String, usize>,
+    /// Files calling `unwrap()`/`expect()` outside test code, most calls first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unwrap_usage: Vec<UnwrapUsage>,
+}
+
+impl ErrorHandlingAnalysis {
+    /// Records one occurrence of `pattern`.
+    pub fn count(&mut self, pattern: &str) {
+        if !self.failure_patterns.iter().any(|p| p == pattern) {
+            self.failure_patterns.push(pattern.to_string());
+        }
+        *self.pattern_counts.entry(pattern.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn count_of(&self, pattern: &str) -> usize {
+        self.pattern_counts.get(pattern).copied().unwrap_or(0)
+    }
+
+    /// Orders stray prints by location and fills in `logging_consistency`.
+    pub fn finalize_logging(&mut self) {
+        self.logging.stray_prints.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        self.logging_consistency = self.logging.verdict();
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct UnwrapUsage {
+    pub file: String,
+    pub unwrap_calls: usize,
+    pub expect_calls: usize,
+}
+
+impl UnwrapUsage {
+    pub fn total(&self) -> usize {
+        self.unwrap_calls + self.expect_calls
+    }
+}
+
+impl Merge for ErrorHandlingAnalysis {
+    fn merge(&mut self, other: Self) {
+        merge_unique(&mut self.failure_patterns, other.failure_patterns);
+        merge_first(&mut self.logging_consistency, other.logging_consistency);
+        self.logging.merge(other.logging);
+        for (pattern, count) in other.pattern_counts {
+            *self.pattern_counts.entry(pattern).or_insert(0) += count;
+        }
+        self.unwrap_usage.extend(other.unwrap_usage);
+    }
 }
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+use serde::{Serialize, Deserialize};
+use super::Merge;
+
+/// A logger is consistent when it makes at least this share of the log calls
+/// and no stray prints are left.
+const CONSISTENT_SHARE: f64 = 0.9;
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct LoggingAnalysis {
+    /// Log calls per logging library, e.g. `zap` or `structlog`.
+    pub logger_calls: BTreeMap<String, usize>,
+    /// Calls passing key/value fields rather than a formatted message.
+    pub structured_calls: usize,
+    pub printf_calls: usize,
+    /// `fmt.Println`, `print()`, `console.log` and the like outside test code.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stray_prints: Vec<StrayPrint>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct StrayPrint {
+    /// Filled in by `ProjectAnalyzer`; language analyzers only see file content.
+    pub file: String,
+    pub line: usize,
+    /// The print call, e.g. `fmt.Println`.
+    pub call: String,
+}
+
+impl LoggingAnalysis {
+    pub fn record_call(&mut self, logger: &str, structured: bool) {
+        *self.logger_calls.entry(logger.to_string()).or_insert(0) += 1;
+        if structured {
+            self.structured_calls += 1;
+        } else {
+            self.printf_calls += 1;
+        }
+    }
+
+    pub fn record_stray_print(&mut self, call: &str, line: usize) {
+        self.stray_prints.push(StrayPrint { file: String::new(), line, call: call.to_string() });
+    }
+
+    /// The logger with the most calls, ties going to the first by name.
+    pub fn dominant_logger(&self) -> Option<(&str, usize)> {
+        self.logger_calls.iter()
+            .fold(None, |best: Option<(&str, usize)>, (logger, &count)| match best {
+                Some((_, best_count)) if best_count >= count => best,
+                _ => Some((logger.as_str(), count)),
+            })
+    }
+
+    /// One-line summary for `ErrorHandlingAnalysis::logging_consistency`;
+    /// empty when nothing was logged or printed.
+    pub fn verdict(&self) -> String {
+        let total: usize = self.logger_calls.values().sum();
+        let strays = self.stray_prints.len();
+        let stray_note = match strays {
+            0 => String::new(),
+            1 => ", 1 stray print call".to_string(),
+            n => format!(", {} stray print calls", n),
+        };
+
+        let Some((dominant, count)) = self.dominant_logger() else {
+            return if strays == 0 {
+                String::new()
+            } else {
+                format!("Inconsistent: no logging library{}", stray_note)
+            };
+        };
+
+        let share = count as f64 / total as f64;
+        let consistent = share >= CONSISTENT_SHARE && strays == 0;
+        let others: Vec<&str> = self.logger_calls.keys().map(String::as_str).filter(|logger| *logger != dominant).collect();
+        let also = if others.is_empty() { String::new() } else { format!("; also {}", others.join(", ")) };
+        format!(
+            "{}: {} ({:.0}% of {} log calls{}), {:.0}% structured{}",
+            if consistent { "Consistent" } else { "Mixed" },
+            dominant,
+            share * 100.0,
+            total,
+            also,
+            self.structured_calls as f64 * 100.0 / total as f64,
+            stray_note,
+        )
+    }
+}
+
+impl Merge for LoggingAnalysis {
+    fn merge(&mut self, other: Self) {
+        for (logger, count) in other.logger_calls {
+            *self.logger_calls.entry(logger).or_insert(0) += count;
+        }
+        self.structured_calls += other.structured_calls;
+        self.printf_calls += other.printf_calls;
+        self.stray_prints.extend(other.stray_prints);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verdict() {
+        let mut logging = LoggingAnalysis::default();
+        assert_eq!(logging.verdict(), "");
+
+        for _ in 0..9 {
+            logging.record_call("zap", true);
+        }
+        logging.record_call("log", false);
+        assert_eq!(logging.verdict(), "Consistent: zap (90% of 10 log calls; also log), 90% structured");
+
+        logging.record_stray_print("fmt.Println", 12);
+        assert_eq!(logging.verdict(), "Mixed: zap (90% of 10 log calls; also log), 90% structured, 1 stray print call");
+
+        let mut prints_only = LoggingAnalysis::default();
+        prints_only.record_stray_print("print", 3);
+        assert_eq!(prints_only.verdict(), "Inconsistent: no logging library, 1 stray print call");
+    }
+}
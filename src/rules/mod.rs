@@ -4,14 +4,19 @@ pub mod testing;
 pub mod config;
 pub mod security;
 pub mod error_handling;
+pub mod logging;
 pub mod architecture;
 pub mod tech_stack;
 
 pub mod design_patterns;
 pub mod dry;
+pub mod commands;
+pub mod diff;
+pub mod custom;
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use serde::{Serialize, Deserialize};
+use crate::parser::SupportedLanguage;
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct AnalysisResult {
@@ -25,6 +30,146 @@ pub struct AnalysisResult {
     pub architecture: architecture::ArchitectureAnalysis,
     pub tech_stack: tech_stack::TechStack,
     pub dry: dry::DRYAnalysis,
-    pub language_counts: HashMap<String, usize>,
+    pub commands: commands::CommandAnalysis,
+    pub language_counts: BTreeMap<String, usize>,
+    pub languages: BTreeMap<SupportedLanguage, LanguageFindings>,
     pub llm_summary: Option<String>,
+    pub diff: Option<diff::DiffAnalysis>,
+    /// Filled in by `ProjectAnalyzer` once the dominant conventions are known.
+    pub naming_violations: naming::NamingViolations,
+    pub custom_rules: custom::CustomRuleAnalysis,
+}
+
+/// Findings that only make sense within a single language, so polyglot
+/// repositories don't blend e.g. Go and TypeScript naming together.
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct LanguageFindings {
+    pub file_count: usize,
+    pub naming: naming::NamingConvention,
+    pub error_handling: error_handling::ErrorHandlingAnalysis,
+    pub testing: testing::TestingAnalysis,
+}
+
+impl LanguageFindings {
+    /// Findings of a single parsed file.
+    pub fn from_file(file_result: &AnalysisResult) -> Self {
+        Self {
+            file_count: 1,
+            naming: file_result.naming.clone(),
+            error_handling: file_result.error_handling.clone(),
+            testing: file_result.testing.clone(),
+        }
+    }
+}
+
+/// Combines results computed independently, e.g. for different files.
+///
+/// Implementations must be associative: lists keep first-seen order, scalar
+/// findings keep the first non-empty value and counters add up. rayon's
+/// `fold`/`reduce` preserve input order, so an associative merge gives the
+/// same result as a sequential pass however the work is split across threads.
+pub trait Merge {
+    fn merge(&mut self, other: Self);
+}
+
+/// Appends the items of `other` not already in `list`.
+pub(crate) fn merge_unique<T: PartialEq>(list: &mut Vec<T>, other: Vec<T>) {
+    for item in other {
+        if !list.contains(&item) {
+            list.push(item);
+        }
+    }
+}
+
+/// Keeps `value` unless it is empty or `N/A`, in which case a non-empty `other` wins.
+pub(crate) fn merge_first(value: &mut String, other: String) {
+    if (value.is_empty() || value == "N/A") && !other.is_empty() {
+        *value = other;
+    }
+}
+
+impl Merge for AnalysisResult {
+    fn merge(&mut self, other: Self) {
+        self.naming.merge(other.naming);
+        self.di.merge(other.di);
+        self.testing.merge(other.testing);
+        self.config.merge(other.config);
+        self.security.merge(other.security);
+        self.error_handling.merge(other.error_handling);
+        self.design_patterns.merge(other.design_patterns);
+        self.architecture.merge(other.architecture);
+        self.tech_stack.merge(other.tech_stack);
+        self.dry.merge(other.dry);
+        self.commands.merge(other.commands);
+        for (language, count) in other.language_counts {
+            *self.language_counts.entry(language).or_insert(0) += count;
+        }
+        for (language, findings) in other.languages {
+            self.languages.entry(language).or_default().merge(findings);
+        }
+        if self.llm_summary.is_none() {
+            self.llm_summary = other.llm_summary;
+        }
+        if self.diff.is_none() {
+            self.diff = other.diff;
+        }
+        self.naming_violations.merge(other.naming_violations);
+        self.custom_rules.merge(other.custom_rules);
+    }
+}
+
+impl Merge for LanguageFindings {
+    fn merge(&mut self, other: Self) {
+        self.file_count += other.file_count;
+        self.naming.merge(other.naming);
+        self.error_handling.merge(other.error_handling);
+        self.testing.merge(other.testing);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::naming::Casing;
+
+    fn file_result(pattern: &str, casing: Casing, framework: &str) -> AnalysisResult {
+        let mut result = AnalysisResult::default();
+        result.design_patterns.patterns.push(pattern.to_string());
+        result.naming.record_function(casing);
+        result.tech_stack.add("framework", framework);
+        *result.language_counts.entry("Go".to_string()).or_insert(0) += 1;
+        result
+    }
+
+    #[test]
+    fn test_merge_does_not_depend_on_grouping() {
+        let mut files = [
+            file_result("Factory", Casing::CamelCase, "Gin"),
+            file_result("Singleton", Casing::SnakeCase, "Gin"),
+            file_result("Factory", Casing::CamelCase, "Echo"),
+        ];
+        for (file, repeated) in files.iter_mut().zip([1, 2, 3]) {
+            file.dry.repeated_literals = repeated;
+        }
+
+        let mut sequential = AnalysisResult::default();
+        for file in files.clone() {
+            sequential.merge(file);
+        }
+
+        let [a, b, c] = files;
+        let mut right = b;
+        right.merge(c);
+        let mut grouped = a;
+        grouped.merge(right);
+        sequential.dry.finalize_score();
+        grouped.dry.finalize_score();
+
+        assert_eq!(serde_json::to_string(&sequential).unwrap(), serde_json::to_string(&grouped).unwrap());
+        assert_eq!(grouped.design_patterns.patterns, vec!["Factory", "Singleton"]);
+        assert_eq!(grouped.tech_stack.frameworks, vec!["Gin", "Echo"]);
+        assert_eq!(grouped.language_counts["Go"], 3);
+        assert_eq!(grouped.dry.repeated_literals, 6);
+        assert_eq!(sequential.dry.duplication_score.to_bits(), grouped.dry.duplication_score.to_bits());
+    }
 }
@@ -1,8 +1,19 @@
 use serde::{Serialize, Deserialize};
 use regex::Regex;
+use std::collections::BTreeMap;
 use std::fmt;
+use std::sync::LazyLock;
+use super::Merge;
+use crate::parser::SupportedLanguage;
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+static CAMEL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-z][a-zA-Z0-9]*$").unwrap());
+static PASCAL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[A-Z][a-zA-Z0-9]*$").unwrap());
+static SNAKE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-z0-9]+(_[a-z0-9]+)*$").unwrap());
+static KEBAB: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-z0-9]+(-[a-z0-9]+)*$").unwrap());
+static UPPER_SNAKE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[A-Z0-9]+(_[A-Z0-9]+)*$").unwrap());
+static LOWERCASE_WORD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-z][a-z0-9]*$").unwrap());
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub enum Casing {
     #[default]
     Unknown,
@@ -32,37 +43,430 @@ impl fmt::Display for Casing {
     }
 }
 
+impl Casing {
+    /// Whether `name` can be read as this casing. Single lowercase words such
+    /// as `run` are valid camelCase, snake_case and kebab-case alike.
+    pub fn accepts(&self, name: &str) -> bool {
+        match self {
+            Casing::CamelCase => CAMEL.is_match(name),
+            Casing::PascalCase => PASCAL.is_match(name),
+            Casing::SnakeCase => SNAKE.is_match(name),
+            Casing::KebabCase => KEBAB.is_match(name),
+            Casing::UpperSnakeCase => UPPER_SNAKE.is_match(name),
+            Casing::Unknown => true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IdentifierKind {
+    Variable,
+    Function,
+    Type,
+    File,
+    /// A constant written in UPPER_SNAKE_CASE by convention, e.g. a Python
+    /// module-level `MAX_RETRIES`. Kept out of the variable vote.
+    Constant,
+}
+
+impl IdentifierKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdentifierKind::Variable => "variable",
+            IdentifierKind::Function => "function",
+            IdentifierKind::Type => "type",
+            IdentifierKind::File => "file",
+            IdentifierKind::Constant => "constant",
+        }
+    }
+}
+
+/// An identifier as written in the source, kept so violations can point at it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct NamedIdentifier {
+    pub kind: IdentifierKind,
+    pub name: String,
+    /// Filled in by `ProjectAnalyzer`; language analyzers only see file content.
+    pub file: String,
+    pub line: usize,
+}
+
+/// An identifier whose casing doesn't match the dominant one for its kind.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct NamingViolation {
+    pub file: String,
+    pub line: usize,
+    pub kind: IdentifierKind,
+    pub name: String,
+    pub expected: Casing,
+    pub found: Casing,
+}
+
+/// Every naming violation in the repository, sorted by location. `total`
+/// is kept when the list is truncated so reports can say how many were left out.
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct NamingViolations {
+    pub total: usize,
+    pub violations: Vec<NamingViolation>,
+}
+
+impl NamingViolations {
+    pub fn new(mut violations: Vec<NamingViolation>) -> Self {
+        violations.sort_by(|a, b| (&a.file, a.line, &a.name).cmp(&(&b.file, b.line, &b.name)));
+        Self { total: violations.len(), violations }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    pub fn retain(&mut self, keep: impl FnMut(&NamingViolation) -> bool) {
+        self.violations.retain(keep);
+        self.total = self.violations.len();
+    }
+
+    /// Keeps the first `max` violations; `total` still counts all of them.
+    pub fn truncate(&mut self, max: usize) {
+        self.violations.truncate(max);
+    }
+}
+
+impl Merge for NamingViolations {
+    fn merge(&mut self, other: Self) {
+        self.total += other.total;
+        self.violations.extend(other.violations);
+    }
+}
+
+/// Occurrence counts of each casing seen for one kind of identifier.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct CasingHistogram {
+    pub counts: BTreeMap<Casing, usize>,
+}
+
+impl CasingHistogram {
+    pub fn record(&mut self, casing: Casing) {
+        if casing != Casing::Unknown {
+            *self.counts.entry(casing).or_insert(0) += 1;
+        }
+    }
+
+    pub fn merge(&mut self, other: &CasingHistogram) {
+        for (casing, count) in &other.counts {
+            *self.counts.entry(*casing).or_insert(0) += count;
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Most frequent casing. Ties are broken by `Casing` declaration order so
+    /// the result never depends on the order files were processed in.
+    pub fn dominant(&self) -> Casing {
+        self.counts
+            .iter()
+            .fold(None, |best: Option<(Casing, usize)>, (casing, count)| match best {
+                Some((_, best_count)) if best_count >= *count => best,
+                _ => Some((*casing, *count)),
+            })
+            .map(|(casing, _)| casing)
+            .unwrap_or_default()
+    }
+
+    /// Share of identifiers using the dominant casing, as a percentage.
+    pub fn confidence(&self) -> f64 {
+        let total = self.total();
+        if total == 0 {
+            return 0.0;
+        }
+        let dominant = self.counts.get(&self.dominant()).copied().unwrap_or(0);
+        dominant as f64 * 100.0 / total as f64
+    }
+}
+
+/// Occurrence counts of each value seen for a naming trait other than
+/// casing, such as an interface prefix or comment style.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct StyleVotes {
+    pub counts: BTreeMap<String, usize>,
+}
+
+impl StyleVotes {
+    pub fn record(&mut self, value: &str) {
+        *self.counts.entry(value.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn merge(&mut self, other: &StyleVotes) {
+        for (value, count) in &other.counts {
+            *self.counts.entry(value.clone()).or_insert(0) += count;
+        }
+    }
+
+    /// Most frequent value, with ties going to the one that sorts first.
+    /// `None` when nothing was recorded or the winner is the empty string,
+    /// which stands for "no such convention".
+    pub fn dominant(&self) -> Option<String> {
+        self.counts
+            .iter()
+            .fold(None, |best: Option<(&String, usize)>, (value, count)| match best {
+                Some((_, best_count)) if best_count >= *count => best,
+                _ => Some((value, *count)),
+            })
+            .map(|(value, _)| value)
+            .filter(|value| !value.is_empty())
+            .cloned()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct NamingConvention {
     pub variable_casing: Casing,
     pub function_casing: Casing,
     pub class_struct_naming: Casing,
     pub file_naming: Casing,
+    pub variable_histogram: CasingHistogram,
+    pub function_histogram: CasingHistogram,
+    pub class_struct_histogram: CasingHistogram,
+    pub file_histogram: CasingHistogram,
     pub comment_style: String,
     pub interface_prefix: Option<String>,
     pub struct_suffix: Option<String>,
+    pub comment_style_votes: StyleVotes,
+    pub interface_prefix_votes: StyleVotes,
+    pub struct_suffix_votes: StyleVotes,
+    /// Every identifier recorded, for violation reporting. `ProjectAnalyzer`
+    /// clears these once violations are known to keep the output small.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub identifiers: Vec<NamedIdentifier>,
 }
 
 impl NamingConvention {
     pub fn detect_casing(name: &str) -> Casing {
-        let camel = Regex::new(r"^[a-z][a-zA-Z0-9]*$").unwrap();
-        let pascal = Regex::new(r"^[A-Z][a-zA-Z0-9]*$").unwrap();
-        let snake = Regex::new(r"^[a-z0-9]+(_[a-z0-9]+)*$").unwrap();
-        let kebab = Regex::new(r"^[a-z0-9]+(-[a-z0-9]+)*$").unwrap();
-        let upper_snake = Regex::new(r"^[A-Z0-9]+(_[A-Z0-9]+)*$").unwrap();
-
-        if upper_snake.is_match(name) && name.contains('_') {
+        if UPPER_SNAKE.is_match(name) && name.contains('_') {
             Casing::UpperSnakeCase
-        } else if camel.is_match(name) {
+        } else if CAMEL.is_match(name) {
             Casing::CamelCase
-        } else if pascal.is_match(name) {
+        } else if PASCAL.is_match(name) {
             Casing::PascalCase
-        } else if snake.is_match(name) {
+        } else if SNAKE.is_match(name) {
             Casing::SnakeCase
-        } else if kebab.is_match(name) {
+        } else if KEBAB.is_match(name) {
             Casing::KebabCase
         } else {
             Casing::Unknown
         }
     }
+
+    /// Records `name` for the majority vote and keeps it for violation reporting.
+    /// Single lowercase words (`result`, `main`) fit camelCase and snake_case
+    /// alike, so they don't vote. Constants have a fixed casing and never vote.
+    pub fn record_identifier(&mut self, kind: IdentifierKind, name: &str, line: usize) {
+        let casing = Self::detect_casing(name);
+        if !LOWERCASE_WORD.is_match(name) {
+            match kind {
+                IdentifierKind::Variable => self.record_variable(casing),
+                IdentifierKind::Function => self.record_function(casing),
+                IdentifierKind::Type => self.record_class_struct(casing),
+                IdentifierKind::File => self.record_file(casing),
+                IdentifierKind::Constant => {}
+            }
+        }
+        self.identifiers.push(NamedIdentifier {
+            kind,
+            name: name.to_string(),
+            file: String::new(),
+            line,
+        });
+    }
+
+    pub fn dominant(&self, kind: IdentifierKind) -> Casing {
+        match kind {
+            IdentifierKind::Variable => self.variable_casing,
+            IdentifierKind::Function => self.function_casing,
+            IdentifierKind::Type => self.class_struct_naming,
+            IdentifierKind::File => self.file_naming,
+            IdentifierKind::Constant => Casing::UpperSnakeCase,
+        }
+    }
+
+    /// Recorded identifiers of `language` that can't be read as the dominant
+    /// casing for their kind. Leading and trailing underscores (`_private`,
+    /// `__init__`) mark visibility rather than casing and are ignored, as does
+    /// the first letter of a Go identifier: exported `NewServer` and unexported
+    /// `handleRequest` are both idiomatic.
+    pub fn violations(&self, language: SupportedLanguage) -> impl Iterator<Item = NamingViolation> + '_ {
+        let visibility_by_case = language == SupportedLanguage::Go;
+        self.identifiers.iter().filter_map(move |identifier| {
+            let expected = self.dominant(identifier.kind);
+            let name = identifier.name.trim_matches('_');
+            let mixed_caps = |casing: Casing| matches!(casing, Casing::CamelCase | Casing::PascalCase);
+            let accepted = expected.accepts(name)
+                || (visibility_by_case && identifier.kind != IdentifierKind::File
+                    && mixed_caps(expected) && mixed_caps(Self::detect_casing(name)));
+            if name.is_empty() || accepted {
+                return None;
+            }
+            Some(NamingViolation {
+                file: identifier.file.clone(),
+                line: identifier.line,
+                kind: identifier.kind,
+                name: identifier.name.clone(),
+                expected,
+                found: Self::detect_casing(name),
+            })
+        })
+    }
+
+    pub fn record_variable(&mut self, casing: Casing) {
+        self.variable_histogram.record(casing);
+        self.variable_casing = self.variable_histogram.dominant();
+    }
+
+    pub fn record_function(&mut self, casing: Casing) {
+        self.function_histogram.record(casing);
+        self.function_casing = self.function_histogram.dominant();
+    }
+
+    pub fn record_class_struct(&mut self, casing: Casing) {
+        self.class_struct_histogram.record(casing);
+        self.class_struct_naming = self.class_struct_histogram.dominant();
+    }
+
+    pub fn record_file(&mut self, casing: Casing) {
+        self.file_histogram.record(casing);
+        self.file_naming = self.file_histogram.dominant();
+    }
+
+    /// Votes on whether interfaces carry an `I` prefix, as in `IUserRepository`.
+    pub fn record_interface(&mut self, name: &str) {
+        let prefixed = name.starts_with('I') && name.chars().nth(1).is_some_and(|c| c.is_uppercase());
+        self.interface_prefix_votes.record(if prefixed { "I" } else { "" });
+        self.interface_prefix = self.interface_prefix_votes.dominant();
+    }
+
+    /// Votes on a struct's name suffix, e.g. "Impl"; `None` for no suffix.
+    pub fn record_struct_suffix(&mut self, suffix: Option<&str>) {
+        self.struct_suffix_votes.record(suffix.unwrap_or_default());
+        self.struct_suffix = self.struct_suffix_votes.dominant();
+    }
+
+    pub fn record_comment_style(&mut self, style: &str) {
+        self.comment_style_votes.record(style);
+        self.comment_style = self.comment_style_votes.dominant().unwrap_or_default();
+    }
+}
+
+impl Merge for NamingConvention {
+    /// Adds another file's counts into this one and re-runs the majority vote.
+    fn merge(&mut self, other: Self) {
+        self.variable_histogram.merge(&other.variable_histogram);
+        self.function_histogram.merge(&other.function_histogram);
+        self.class_struct_histogram.merge(&other.class_struct_histogram);
+        self.file_histogram.merge(&other.file_histogram);
+
+        self.variable_casing = self.variable_histogram.dominant();
+        self.function_casing = self.function_histogram.dominant();
+        self.class_struct_naming = self.class_struct_histogram.dominant();
+        self.file_naming = self.file_histogram.dominant();
+
+        self.comment_style_votes.merge(&other.comment_style_votes);
+        self.interface_prefix_votes.merge(&other.interface_prefix_votes);
+        self.struct_suffix_votes.merge(&other.struct_suffix_votes);
+
+        self.comment_style = self.comment_style_votes.dominant().unwrap_or_default();
+        self.interface_prefix = self.interface_prefix_votes.dominant();
+        self.struct_suffix = self.struct_suffix_votes.dominant();
+        self.identifiers.extend(other.identifiers);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dominant_casing_is_majority() {
+        let mut naming = NamingConvention::default();
+        naming.record_function(Casing::SnakeCase);
+        naming.record_function(Casing::CamelCase);
+        naming.record_function(Casing::CamelCase);
+        naming.record_function(Casing::Unknown);
+
+        assert_eq!(naming.function_casing, Casing::CamelCase);
+        assert_eq!(naming.function_histogram.total(), 3);
+        assert!((naming.function_histogram.confidence() - 66.666).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_violations_allow_ambiguous_names() {
+        let mut naming = NamingConvention::default();
+        naming.record_identifier(IdentifierKind::Function, "load_data", 1);
+        naming.record_identifier(IdentifierKind::Function, "save_data", 2);
+        naming.record_identifier(IdentifierKind::Function, "run", 3);
+        naming.record_identifier(IdentifierKind::Function, "fetchData", 4);
+        naming.record_identifier(IdentifierKind::Function, "parse_input", 5);
+
+        let violations: Vec<NamingViolation> = naming.violations(SupportedLanguage::Python).collect();
+        assert_eq!(naming.function_casing, Casing::SnakeCase);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].name, "fetchData");
+        assert_eq!(violations[0].line, 4);
+        assert_eq!(violations[0].found, Casing::CamelCase);
+    }
+
+    #[test]
+    fn test_go_accepts_exported_and_unexported_names() {
+        let mut naming = NamingConvention::default();
+        for (line, name) in ["NewServer", "Start", "Shutdown", "handler", "handleRequest", "main", "load_config"].iter().enumerate() {
+            naming.record_identifier(IdentifierKind::Function, name, line + 1);
+        }
+
+        assert_eq!(naming.function_casing, Casing::PascalCase);
+        let violations: Vec<NamingViolation> = naming.violations(SupportedLanguage::Go).collect();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].name, "load_config");
+        assert_eq!(naming.violations(SupportedLanguage::TypeScript).count(), 4);
+    }
+
+    #[test]
+    fn test_merge_is_order_independent() {
+        let mut a = NamingConvention::default();
+        a.record_variable(Casing::SnakeCase);
+        let mut b = NamingConvention::default();
+        b.record_variable(Casing::CamelCase);
+
+        let mut ab = a.clone();
+        ab.merge(b.clone());
+        let mut ba = b;
+        ba.merge(a);
+
+        assert_eq!(ab.variable_casing, ba.variable_casing);
+        assert_eq!(ab.variable_histogram, ba.variable_histogram);
+    }
+
+    #[test]
+    fn test_interface_prefix_and_comment_style_are_voted() {
+        let mut a = NamingConvention::default();
+        a.record_interface("IUserRepository");
+        a.record_comment_style("GoDoc");
+        let mut b = NamingConvention::default();
+        for name in ["OrderRepository", "Clock", "Inventory"] {
+            b.record_interface(name);
+        }
+        b.record_comment_style("Block comments");
+        b.record_comment_style("Block comments");
+
+        let mut ab = a.clone();
+        ab.merge(b.clone());
+        let mut ba = b;
+        ba.merge(a.clone());
+
+        assert_eq!(a.interface_prefix.as_deref(), Some("I"));
+        assert_eq!(ab.interface_prefix, None);
+        assert_eq!(ab.interface_prefix_votes, ba.interface_prefix_votes);
+        assert_eq!(ab.comment_style, "Block comments");
+        assert_eq!(ba.comment_style, "Block comments");
+        assert_eq!(ab.struct_suffix, None);
+    }
 }
@@ -1,9 +1,48 @@
 use serde::{Serialize, Deserialize};
+use super::{merge_first, merge_unique, Merge};
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct SecurityAnalysis {
-    pub hardcoded_secrets: Vec<String>,
+    pub hardcoded_secrets: Vec<SecretFinding>,
+    /// Findings hidden because they are recorded in the secrets baseline.
+    pub baselined_secrets: usize,
     pub input_sanitization: String,
     pub memory_safety: String,
     pub concurrency_safety: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unsafe_blocks: Vec<UnsafeBlock>,
+    /// Shared-state and message-passing primitives, e.g. `Arc<Mutex>`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub concurrency_primitives: Vec<String>,
+}
+
+impl Merge for SecurityAnalysis {
+    fn merge(&mut self, other: Self) {
+        self.hardcoded_secrets.extend(other.hardcoded_secrets);
+        self.baselined_secrets += other.baselined_secrets;
+        merge_first(&mut self.input_sanitization, other.input_sanitization);
+        merge_first(&mut self.memory_safety, other.memory_safety);
+        merge_first(&mut self.concurrency_safety, other.concurrency_safety);
+        self.unsafe_blocks.extend(other.unsafe_blocks);
+        merge_unique(&mut self.concurrency_primitives, other.concurrency_primitives);
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct SecretFinding {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub rule_id: String,
+    pub message: String,
+    /// The matched value with everything but a short prefix masked out.
+    pub redacted_preview: String,
+    /// Stable hash of file, rule and secret value; unaffected by line shifts.
+    pub fingerprint: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct UnsafeBlock {
+    pub file: String,
+    pub line: usize,
 }
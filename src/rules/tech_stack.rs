@@ -1,9 +1,147 @@
+use std::collections::BTreeSet;
+use std::fmt;
 use serde::{Serialize, Deserialize};
+use super::Merge;
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct TechStack {
-    pub frameworks: Vec<String>,
-    pub libraries: Vec<String>,
-    pub databases: Vec<String>,
-    pub build_tools: Vec<String>,
+    pub frameworks: Vec<TechEntry>,
+    pub libraries: Vec<TechEntry>,
+    pub databases: Vec<TechEntry>,
+    pub build_tools: Vec<TechEntry>,
+}
+
+/// A detected technology. The version is kept apart from the name, since
+/// display names such as "PostgreSQL (lib/pq)" have parentheses of their own.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct TechEntry {
+    pub name: String,
+    /// Every version declared for it, sorted. A single version can itself
+    /// contain commas, e.g. Cargo's `>=1.2, <1.5`.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub versions: BTreeSet<String>,
+}
+
+impl TechEntry {
+    fn joined_versions(&self) -> String {
+        self.versions.iter().map(String::as_str).collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// `name (version, version)`, or just the name when no version is known.
+impl fmt::Display for TechEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.versions.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{} ({})", self.name, self.joined_versions())
+        }
+    }
+}
+
+/// Compares with the displayed form, e.g. `"Gin (v1.9.1)"`.
+impl PartialEq<&str> for TechEntry {
+    fn eq(&self, other: &&str) -> bool {
+        if self.versions.is_empty() {
+            return self.name == *other;
+        }
+        other.strip_prefix(self.name.as_str())
+            .and_then(|rest| rest.strip_prefix(" ("))
+            .and_then(|rest| rest.strip_suffix(')'))
+            .is_some_and(|rest| rest == self.joined_versions())
+    }
+}
+
+impl TechStack {
+    /// Records `name` under `category` ("framework", "database", "library" or
+    /// "build_tool"), skipping duplicates.
+    pub fn add(&mut self, category: &str, name: &str) {
+        self.add_versioned(category, name, None);
+    }
+
+    /// Like `add`, but shown as `name (version)`. A version declared in a
+    /// manifest replaces an unversioned entry found through imports; manifests
+    /// declaring different versions are all listed, e.g. `Gin (v1.8.0, v1.9.1)`.
+    pub fn add_versioned(&mut self, category: &str, name: &str, version: Option<&str>) {
+        let Some(entry) = self.entry(category, name) else { return };
+        if let Some(version) = version.map(str::trim).filter(|v| !v.is_empty()) {
+            entry.versions.insert(version.to_string());
+        }
+    }
+
+    /// The entry for `name` under `category`, added if it isn't there yet.
+    fn entry(&mut self, category: &str, name: &str) -> Option<&mut TechEntry> {
+        let list = match category {
+            "framework" => &mut self.frameworks,
+            "database" => &mut self.databases,
+            "library" => &mut self.libraries,
+            "build_tool" => &mut self.build_tools,
+            _ => return None,
+        };
+        let index = match list.iter().position(|item| item.name == name) {
+            Some(index) => index,
+            None => {
+                list.push(TechEntry { name: name.to_string(), versions: BTreeSet::new() });
+                list.len() - 1
+            }
+        };
+        Some(&mut list[index])
+    }
+}
+
+impl Merge for TechStack {
+    fn merge(&mut self, other: Self) {
+        let categories = [
+            ("framework", other.frameworks),
+            ("library", other.libraries),
+            ("database", other.databases),
+            ("build_tool", other.build_tools),
+        ];
+        for (category, entries) in categories {
+            for entry in entries {
+                if let Some(existing) = self.entry(category, &entry.name) {
+                    existing.versions.extend(entry.versions);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_keeps_entries_and_versions_in_any_order() {
+        let mut a = TechStack::default();
+        a.add_versioned("database", "PostgreSQL (lib/pq)", Some("v1.10.9"));
+        a.add_versioned("framework", "Gin", Some("v1.9.1"));
+        let mut b = TechStack::default();
+        b.add_versioned("database", "PostgreSQL (psycopg2)", Some("2.9.9"));
+        b.add_versioned("framework", "Gin", Some("v1.8.0"));
+        b.add("framework", "Gin");
+
+        let mut ab = a.clone();
+        ab.merge(b.clone());
+        let mut ba = b;
+        ba.merge(a);
+
+        assert_eq!(ab.frameworks, vec!["Gin (v1.8.0, v1.9.1)"]);
+        assert_eq!(ab.frameworks, ba.frameworks);
+        assert_eq!(ab.databases, vec!["PostgreSQL (lib/pq) (v1.10.9)", "PostgreSQL (psycopg2) (2.9.9)"]);
+        assert_eq!(ba.databases, vec!["PostgreSQL (psycopg2) (2.9.9)", "PostgreSQL (lib/pq) (v1.10.9)"]);
+    }
+
+    #[test]
+    fn test_version_requirements_with_commas_stay_whole() {
+        let mut a = TechStack::default();
+        a.add_versioned("library", "Serde", Some(">=1.2, <1.5"));
+        let mut b = TechStack::default();
+        b.add_versioned("library", "Serde", Some("1.0"));
+        a.merge(b);
+
+        let serde = &a.libraries[0];
+        assert_eq!(serde.versions.iter().collect::<Vec<_>>(), vec!["1.0", ">=1.2, <1.5"]);
+        assert_eq!(serde.to_string(), "Serde (1.0, >=1.2, <1.5)");
+    }
 }
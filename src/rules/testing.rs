@@ -1,4 +1,5 @@
 use serde::{Serialize, Deserialize};
+use super::{merge_first, Merge};
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct TestingAnalysis {
@@ -7,3 +8,12 @@ pub struct TestingAnalysis {
     pub naming_pattern: String,
     pub assertion_style: String,
 }
+
+impl Merge for TestingAnalysis {
+    fn merge(&mut self, other: Self) {
+        merge_first(&mut self.test_location, other.test_location);
+        merge_first(&mut self.mocking_strategy, other.mocking_strategy);
+        merge_first(&mut self.naming_pattern, other.naming_pattern);
+        merge_first(&mut self.assertion_style, other.assertion_style);
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+use serde::Serialize;
+use crate::rules::AnalysisResult;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+const SECRET_RULE_ID: &str = "buddy/hardcoded-secret";
+const DUPLICATION_RULE_ID: &str = "buddy/duplicated-block";
+const CLONE_RULE_ID: &str = "buddy/code-clone";
+const NAMING_RULE_ID: &str = "buddy/naming-convention";
+
+#[derive(Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Serialize)]
+struct Run {
+    tool: Tool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    rules: Vec<Rule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: &'static str,
+    short_description: Message,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: &'static str,
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    partial_fingerprints: BTreeMap<&'static str, String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_line: Option<usize>,
+}
+
+pub struct SarifGenerator;
+
+impl SarifGenerator {
+    /// Renders hardcoded secrets, duplicated blocks, code clones and naming
+    /// violations as a SARIF 2.1.0 log for code-scanning tools.
+    pub fn generate(result: &AnalysisResult) -> anyhow::Result<String> {
+        let mut results = Vec::new();
+
+        for secret in &result.security.hardcoded_secrets {
+            results.push(SarifResult {
+                rule_id: SECRET_RULE_ID,
+                level: "error",
+                message: Message {
+                    text: format!("{} ({}): {}", secret.message, secret.rule_id, secret.redacted_preview),
+                },
+                locations: vec![Self::location(&secret.file, secret.line, Some(secret.column))],
+                partial_fingerprints: BTreeMap::from([("buddySecret/v2", secret.fingerprint.clone())]),
+            });
+        }
+
+        for block in &result.dry.duplicated_blocks {
+            results.push(SarifResult {
+                rule_id: DUPLICATION_RULE_ID,
+                level: "warning",
+                message: Message { text: block.description.clone() },
+                locations: vec![Self::location(&block.file, block.line, None)],
+                partial_fingerprints: BTreeMap::new(),
+            });
+        }
+
+        for clone in &result.dry.clones {
+            let mut location = Self::location(&clone.duplicate.file, clone.duplicate.start_line, None);
+            location.physical_location.region.end_line = Some(clone.duplicate.end_line);
+            results.push(SarifResult {
+                rule_id: CLONE_RULE_ID,
+                level: "note",
+                message: Message {
+                    text: format!(
+                        "{} clone of {}:{}-{} ({} tokens)",
+                        clone.clone_type.as_str(), clone.original.file, clone.original.start_line,
+                        clone.original.end_line, clone.token_count
+                    ),
+                },
+                locations: vec![location],
+                partial_fingerprints: BTreeMap::new(),
+            });
+        }
+
+        for violation in &result.naming_violations.violations {
+            results.push(SarifResult {
+                rule_id: NAMING_RULE_ID,
+                level: "warning",
+                message: Message {
+                    text: format!(
+                        "{} `{}` is {}, expected {}",
+                        violation.kind.as_str(), violation.name, violation.found, violation.expected
+                    ),
+                },
+                locations: vec![Self::location(&violation.file, violation.line, None)],
+                partial_fingerprints: BTreeMap::new(),
+            });
+        }
+
+        let log = SarifLog {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: "buddy",
+                        version: env!("CARGO_PKG_VERSION"),
+                        rules: vec![
+                            Rule {
+                                id: SECRET_RULE_ID,
+                                short_description: Message { text: "Hardcoded secret".to_string() },
+                            },
+                            Rule {
+                                id: DUPLICATION_RULE_ID,
+                                short_description: Message { text: "Duplicated code block".to_string() },
+                            },
+                            Rule {
+                                id: CLONE_RULE_ID,
+                                short_description: Message { text: "Cross-file code clone".to_string() },
+                            },
+                            Rule {
+                                id: NAMING_RULE_ID,
+                                short_description: Message { text: "Identifier breaks the dominant naming convention".to_string() },
+                            },
+                        ],
+                    },
+                },
+                results,
+            }],
+        };
+
+        Ok(serde_json::to_string_pretty(&log)?)
+    }
+
+    fn location(file: &str, line: usize, column: Option<usize>) -> Location {
+        Location {
+            physical_location: PhysicalLocation {
+                artifact_location: ArtifactLocation { uri: file.to_string() },
+                region: Region { start_line: line.max(1), start_column: column, end_line: None },
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::security::SecretFinding;
+
+    #[test]
+    fn test_secret_becomes_sarif_result() {
+        let mut result = AnalysisResult::default();
+        result.security.hardcoded_secrets.push(SecretFinding {
+            file: "src/config.go".to_string(),
+            line: 12,
+            column: 5,
+            rule_id: "aws-access-key-id".to_string(),
+            message: "AWS access key ID".to_string(),
+            redacted_preview: "AKIA****".to_string(),
+            fingerprint: "0123456789abcdef".to_string(),
+        });
+
+        let sarif: serde_json::Value = serde_json::from_str(&SarifGenerator::generate(&result).unwrap()).unwrap();
+        let first = &sarif["runs"][0]["results"][0];
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(first["ruleId"], SECRET_RULE_ID);
+        assert_eq!(first["locations"][0]["physicalLocation"]["artifactLocation"]["uri"], "src/config.go");
+        assert_eq!(first["locations"][0]["physicalLocation"]["region"]["startLine"], 12);
+        assert_eq!(first["locations"][0]["physicalLocation"]["region"]["startColumn"], 5);
+    }
+}
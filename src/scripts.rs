@@ -0,0 +1,350 @@
+use std::sync::LazyLock;
+use regex::Regex;
+use crate::rules::AnalysisResult;
+use crate::rules::commands::{CommandAnalysis, CommandKind, ProjectCommand};
+
+static MAKE_TARGET: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^([A-Za-z0-9][A-Za-z0-9_.-]*)\s*:([^=]|$)").unwrap());
+static JUST_RECIPE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^@?([A-Za-z0-9][A-Za-z0-9_-]*)(\s+[^:]*)?:([^=]|$)").unwrap());
+static TASKFILE_TASK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^  ([A-Za-z0-9][A-Za-z0-9_:-]*):\s*(#.*)?$").unwrap());
+static TOX_ENV: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\[testenv:([^\]]+)\]").unwrap());
+static NOX_SESSION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"@nox\.session[\s\S]*?\ndef\s+([A-Za-z_][A-Za-z0-9_]*)").unwrap());
+
+/// Scripts npm runs itself around `npm install`, `npm publish` and the like.
+const NPM_LIFECYCLE_SCRIPTS: &[&str] = &[
+    "preinstall", "install", "postinstall", "preuninstall", "uninstall", "postuninstall",
+    "prepare", "prepublish", "prepublishOnly", "prepack", "postpack",
+    "preversion", "version", "postversion", "dependencies",
+];
+
+/// Reads the commands a project actually defines: `package.json` scripts,
+/// Makefile/justfile/Taskfile targets, Cargo workspaces, tox/nox sessions and
+/// CI workflow steps.
+pub struct ScriptAnalyzer;
+
+impl ScriptAnalyzer {
+    pub fn is_script_source(relative_path: &str) -> bool {
+        let file_name = relative_path.rsplit('/').next().unwrap_or(relative_path);
+        matches!(
+            file_name,
+            "package.json" | "Makefile" | "makefile" | "GNUmakefile" | "justfile" | "Justfile" | ".justfile"
+                | "Taskfile.yml" | "Taskfile.yaml" | "Cargo.toml" | "tox.ini" | "noxfile.py" | ".gitlab-ci.yml"
+        ) || Self::is_github_workflow(relative_path)
+    }
+
+    pub fn analyze(relative_path: &str, content: &str, commands: &mut CommandAnalysis) {
+        let file_name = relative_path.rsplit('/').next().unwrap_or(relative_path);
+        let found: Vec<(String, CommandKind)> = match file_name {
+            "package.json" => Self::parse_package_json(content),
+            "Makefile" | "makefile" | "GNUmakefile" => Self::parse_targets(content, &MAKE_TARGET, "make"),
+            "justfile" | "Justfile" | ".justfile" => Self::parse_targets(content, &JUST_RECIPE, "just"),
+            "Taskfile.yml" | "Taskfile.yaml" => Self::parse_taskfile(content),
+            "Cargo.toml" => Self::parse_cargo_toml(content),
+            "tox.ini" => Self::parse_tox(content),
+            "noxfile.py" => NOX_SESSION.captures_iter(content)
+                .map(|c| (format!("nox -s {}", &c[1]), Self::classify(&c[1])))
+                .collect(),
+            ".gitlab-ci.yml" => Self::parse_ci_steps(content, &["script:", "before_script:"]),
+            _ if Self::is_github_workflow(relative_path) => Self::parse_ci_steps(content, &["run:"]),
+            _ => return,
+        };
+
+        for (command, kind) in found {
+            commands.commands.push(ProjectCommand {
+                command,
+                kind,
+                source: relative_path.to_string(),
+            });
+        }
+    }
+
+    /// Rewrites `npm` invocations for the detected package manager and points
+    /// commands defined in subdirectories at their directory, then sorts and
+    /// de-duplicates so the output doesn't depend on file processing order.
+    pub fn finalize(result: &mut AnalysisResult) {
+        let runner = ["pnpm", "Yarn", "Bun"].into_iter()
+            .find(|tool| result.tech_stack.build_tools.iter().any(|b| b.name == *tool))
+            .map(|tool| tool.to_lowercase());
+        let commands = &mut result.commands.commands;
+
+        for command in commands.iter_mut() {
+            if command.source.ends_with("package.json") {
+                if let Some((rest, runner)) = command.command.strip_prefix("npm ").zip(runner.as_deref()) {
+                    command.command = format!("{} {}", runner, rest);
+                }
+            }
+            // A nested Makefile or package.json runs from its own directory;
+            // CI steps run from the repository root wherever the workflow lives.
+            if let Some((directory, _)) = command.source.rsplit_once('/').filter(|_| !Self::is_github_workflow(&command.source)) {
+                command.command = format!("cd {} && {}", directory, command.command);
+            }
+        }
+
+        // Scripts from the repository root first, then by path.
+        commands.sort_by(|a, b| {
+            (a.source.matches('/').count(), &a.source, a.kind, &a.command)
+                .cmp(&(b.source.matches('/').count(), &b.source, b.kind, &b.command))
+        });
+        let mut seen = std::collections::HashSet::new();
+        commands.retain(|c| seen.insert(c.command.clone()));
+    }
+
+    fn is_github_workflow(relative_path: &str) -> bool {
+        relative_path.starts_with(".github/workflows/")
+            && (relative_path.ends_with(".yml") || relative_path.ends_with(".yaml"))
+    }
+
+    fn classify(name: &str) -> CommandKind {
+        let name = name.to_lowercase();
+        if ["test", "spec", "pytest", "e2e", "coverage"].iter().any(|k| name.contains(k)) {
+            CommandKind::Test
+        } else if ["lint", "fmt", "format", "clippy", "vet", "check", "typecheck"].iter().any(|k| name.contains(k)) {
+            CommandKind::Lint
+        } else if ["build", "compile", "dist", "package", "bundle", "release"].iter().any(|k| name.contains(k)) {
+            CommandKind::Build
+        } else {
+            CommandKind::Other
+        }
+    }
+
+    fn classify_word(word: &str) -> CommandKind {
+        match word {
+            "test" | "pytest" | "jest" | "vitest" | "tox" | "nox" => CommandKind::Test,
+            "lint" | "fmt" | "clippy" | "vet" | "eslint" | "ruff" | "flake8" | "mypy" | "golangci" => CommandKind::Lint,
+            "build" | "compile" | "dist" | "bundle" => CommandKind::Build,
+            _ => CommandKind::Other,
+        }
+    }
+
+    fn parse_package_json(content: &str) -> Vec<(String, CommandKind)> {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(content) else { return Vec::new() };
+        let Some(scripts) = value.get("scripts").and_then(|s| s.as_object()) else { return Vec::new() };
+
+        // Hooks run implicitly, e.g. `pretest` with `npm test`, and aren't meant
+        // to be invoked directly. `preview` or `postcss` without a `view` or
+        // `css` script are ordinary scripts.
+        let is_hook = |name: &str| NPM_LIFECYCLE_SCRIPTS.contains(&name)
+            || ["pre", "post"].iter().any(|prefix| name.strip_prefix(prefix).is_some_and(|hooked| scripts.contains_key(hooked)));
+        scripts.keys()
+            .filter(|name| !is_hook(name))
+            .map(|name| {
+                let command = match name.as_str() {
+                    "test" | "start" => format!("npm {}", name),
+                    _ => format!("npm run {}", name),
+                };
+                (command, Self::classify(name))
+            })
+            .collect()
+    }
+
+    fn parse_targets(content: &str, pattern: &Regex, tool: &str) -> Vec<(String, CommandKind)> {
+        content.lines()
+            .filter_map(|line| pattern.captures(line))
+            .map(|c| c[1].to_string())
+            .filter(|target| !target.starts_with('.'))
+            .map(|target| (format!("{} {}", tool, target), Self::classify(&target)))
+            .collect()
+    }
+
+    fn parse_taskfile(content: &str) -> Vec<(String, CommandKind)> {
+        let mut in_tasks = false;
+        let mut found = Vec::new();
+        for line in content.lines() {
+            if !line.starts_with(' ') && !line.trim().is_empty() {
+                in_tasks = line.trim_end() == "tasks:";
+                continue;
+            }
+            if in_tasks {
+                if let Some(c) = TASKFILE_TASK.captures(line) {
+                    found.push((format!("task {}", &c[1]), Self::classify(&c[1])));
+                }
+            }
+        }
+        found
+    }
+
+    fn parse_cargo_toml(content: &str) -> Vec<(String, CommandKind)> {
+        let Ok(value) = content.parse::<toml::Table>() else { return Vec::new() };
+        let members: Vec<String> = value.get("workspace")
+            .and_then(|w| w.get("members"))
+            .and_then(|m| m.as_array())
+            .map(|list| list.iter().filter_map(|m| m.as_str()).map(str::to_string).collect())
+            .unwrap_or_default();
+
+        if members.is_empty() {
+            if value.get("package").is_none() {
+                return Vec::new();
+            }
+            return vec![
+                ("cargo build".to_string(), CommandKind::Build),
+                ("cargo test".to_string(), CommandKind::Test),
+            ];
+        }
+
+        // Members are listed through their own Cargo.toml, whose package name
+        // needn't match the directory name.
+        vec![
+            ("cargo build --workspace".to_string(), CommandKind::Build),
+            ("cargo test --workspace".to_string(), CommandKind::Test),
+        ]
+    }
+
+    fn parse_tox(content: &str) -> Vec<(String, CommandKind)> {
+        let mut found = Vec::new();
+        if content.lines().any(|l| l.trim() == "[tox]") {
+            found.push(("tox".to_string(), CommandKind::Test));
+        }
+        for line in content.lines() {
+            if let Some(c) = TOX_ENV.captures(line.trim()) {
+                let env = &c[1];
+                let kind = match Self::classify(env) {
+                    CommandKind::Other => CommandKind::Test,
+                    kind => kind,
+                };
+                found.push((format!("tox -e {}", env), kind));
+            }
+        }
+        found
+    }
+
+    /// Pulls shell commands out of CI steps. Only commands recognisable as
+    /// build, test or lint steps are kept; setup noise like `apt-get` is not.
+    fn parse_ci_steps(content: &str, keys: &[&str]) -> Vec<(String, CommandKind)> {
+        let mut found = Vec::new();
+        let mut block_indent: Option<usize> = None;
+
+        for line in content.lines() {
+            let indent = line.len() - line.trim_start().len();
+            let trimmed = line.trim().trim_start_matches("- ").trim();
+
+            if let Some(min_indent) = block_indent {
+                if trimmed.is_empty() {
+                    continue;
+                }
+                if indent >= min_indent {
+                    Self::push_ci_command(trimmed, &mut found);
+                    continue;
+                }
+                block_indent = None;
+            }
+
+            let Some(key) = keys.iter().find(|k| trimmed.starts_with(**k)) else { continue };
+            let value = trimmed[key.len()..].trim();
+            if value.is_empty() || value == "|" || value == ">" {
+                block_indent = Some(indent + 1);
+            } else {
+                Self::push_ci_command(value.trim_matches(|c| c == '"' || c == '\''), &mut found);
+            }
+        }
+        found
+    }
+
+    fn push_ci_command(command: &str, found: &mut Vec<(String, CommandKind)>) {
+        let command = command.trim_start_matches("- ").trim();
+        if command.is_empty() || command.starts_with('#') {
+            return;
+        }
+        // Classify on whole words so `protobuf-compiler` isn't taken for a build step.
+        let kind = command.split(|c: char| !c.is_ascii_alphanumeric())
+            .map(Self::classify_word)
+            .find(|kind| *kind != CommandKind::Other)
+            .unwrap_or(CommandKind::Other);
+        if kind != CommandKind::Other {
+            found.push((command.to_string(), kind));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commands(path: &str, content: &str) -> Vec<(String, CommandKind)> {
+        let mut analysis = CommandAnalysis::default();
+        ScriptAnalyzer::analyze(path, content, &mut analysis);
+        analysis.commands.into_iter().map(|c| (c.command, c.kind)).collect()
+    }
+
+    #[test]
+    fn test_package_json_scripts() {
+        let content = r#"{ "scripts": {
+            "build": "tsc", "test": "vitest run", "pretest": "tsc", "postbuild": "cp -r assets dist", "lint": "eslint .",
+            "preview": "vite preview", "prettier": "prettier --write .", "postcss": "postcss src -d dist", "postinstall": "husky"
+        } }"#;
+        let found = commands("package.json", content);
+
+        assert!(found.contains(&("npm run build".to_string(), CommandKind::Build)));
+        assert!(found.contains(&("npm test".to_string(), CommandKind::Test)));
+        assert!(found.contains(&("npm run lint".to_string(), CommandKind::Lint)));
+        for script in ["preview", "prettier", "postcss"] {
+            assert!(found.iter().any(|(c, _)| *c == format!("npm run {}", script)), "{} is missing", script);
+        }
+        assert!(!found.iter().any(|(c, _)| c.contains("pretest") || c.contains("postbuild") || c.contains("postinstall")));
+    }
+
+    #[test]
+    fn test_makefile_and_justfile_targets() {
+        let makefile = ".PHONY: build test\nVERSION := 1.0\nbuild:\n\tgo build ./...\ntest: build\n\tgo test ./...\n";
+        assert_eq!(commands("Makefile", makefile), vec![
+            ("make build".to_string(), CommandKind::Build),
+            ("make test".to_string(), CommandKind::Test),
+        ]);
+
+        let justfile = "set shell := [\"bash\"]\n\nlint:\n    cargo clippy\n\ntest filter='':\n    cargo test {{filter}}\n";
+        assert_eq!(commands("justfile", justfile), vec![
+            ("just lint".to_string(), CommandKind::Lint),
+            ("just test".to_string(), CommandKind::Test),
+        ]);
+    }
+
+    #[test]
+    fn test_github_workflow_steps() {
+        let workflow = r#"
+jobs:
+  ci:
+    steps:
+      - uses: actions/checkout@v4
+      - run: sudo apt-get install -y protobuf-compiler
+      - run: cargo build --workspace
+      - name: Test
+        run: |
+          cargo test --workspace
+          cargo clippy -- -D warnings
+"#;
+        assert_eq!(commands(".github/workflows/ci.yml", workflow), vec![
+            ("cargo build --workspace".to_string(), CommandKind::Build),
+            ("cargo test --workspace".to_string(), CommandKind::Test),
+            ("cargo clippy -- -D warnings".to_string(), CommandKind::Lint),
+        ]);
+    }
+
+    #[test]
+    fn test_finalize_uses_detected_package_manager() {
+        let mut result = AnalysisResult::default();
+        result.tech_stack.add("build_tool", "pnpm");
+        ScriptAnalyzer::analyze("web/package.json", r#"{ "scripts": { "test": "jest" } }"#, &mut result.commands);
+        ScriptAnalyzer::analyze("package.json", r#"{ "scripts": { "test": "jest" } }"#, &mut result.commands);
+        ScriptAnalyzer::finalize(&mut result);
+
+        let found: Vec<(&str, &str)> = result.commands.commands.iter().map(|c| (c.command.as_str(), c.source.as_str())).collect();
+        assert_eq!(found, vec![("pnpm test", "package.json"), ("cd web && pnpm test", "web/package.json")]);
+    }
+
+    #[test]
+    fn test_nested_sources_run_from_their_directory() {
+        let mut result = AnalysisResult::default();
+        let makefile = "test:\n\tgo test ./...\n";
+        ScriptAnalyzer::analyze("services/api/Makefile", makefile, &mut result.commands);
+        ScriptAnalyzer::analyze("Makefile", makefile, &mut result.commands);
+        ScriptAnalyzer::analyze("crates/core/Cargo.toml", "[package]\nname = \"myapp-core\"\n", &mut result.commands);
+        ScriptAnalyzer::analyze(".github/workflows/ci.yml", "steps:\n  - run: make test\n", &mut result.commands);
+        ScriptAnalyzer::finalize(&mut result);
+
+        let found: Vec<(&str, &str)> = result.commands.commands.iter().map(|c| (c.command.as_str(), c.source.as_str())).collect();
+        assert_eq!(found, vec![
+            ("make test", "Makefile"),
+            ("cd crates/core && cargo build", "crates/core/Cargo.toml"),
+            ("cd crates/core && cargo test", "crates/core/Cargo.toml"),
+            ("cd services/api && make test", "services/api/Makefile"),
+        ]);
+    }
+}